@@ -1 +1 @@
-cargo watch -c -q -x run
\ No newline at end of file
+cargo watch -c -q -x "run -- build samples"
\ No newline at end of file
@@ -1,10 +1,14 @@
-use crate::span::{Span, Spanned};
+use crate::{
+    span::{Span, Spanned},
+    token::Comment,
+};
 
 #[derive(Debug, Clone)]
 pub struct Root {
     pub name: String,
     pub path: String,
     pub stmts: Vec<Stmt>,
+    pub comments: Vec<Comment>,
     pub span: Span,
 }
 
@@ -16,6 +20,8 @@ impl Spanned for Root {
 
 #[derive(Debug, Clone)]
 pub enum Stmt {
+    Use(Use),
+    Import(Import),
     Mod(Mod),
     Let(Let),
     Enum(Enum),
@@ -25,7 +31,11 @@ pub enum Stmt {
     MemberAccess(MemberAccess),
     Return(Return),
     LitJs(LitJs),
+    If(If),
+    Match(Match),
     Noop,
+    /// Placeholder for a statement that failed to parse.
+    Error(Span),
 }
 
 impl From<Expr> for Stmt {
@@ -38,6 +48,18 @@ impl From<Expr> for Stmt {
         }
     }
 }
+impl From<Use> for Stmt {
+    fn from(val: Use) -> Self {
+        Stmt::Use(val)
+    }
+}
+
+impl From<Import> for Stmt {
+    fn from(val: Import) -> Self {
+        Stmt::Import(val)
+    }
+}
+
 impl From<Mod> for Stmt {
     fn from(val: Mod) -> Self {
         Stmt::Mod(val)
@@ -95,6 +117,8 @@ impl From<LitJs> for Stmt {
 impl Spanned for Stmt {
     fn span(&self) -> Span {
         match self {
+            Stmt::Use(v) => v.span(),
+            Stmt::Import(v) => v.span(),
             Stmt::Mod(v) => v.span(),
             Stmt::Let(v) => v.span(),
             Stmt::Enum(v) => v.span(),
@@ -104,11 +128,80 @@ impl Spanned for Stmt {
             Stmt::MemberAccess(v) => v.span(),
             Stmt::Return(v) => v.span(),
             Stmt::LitJs(v) => v.span(),
-            Stmt::Noop => todo!(),
+            Stmt::If(v) => v.span(),
+            Stmt::Match(v) => v.span(),
+            Stmt::Noop => Span::empty(),
+            Stmt::Error(span) => span.clone(),
         }
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Use {
+    pub path: UsePath,
+    pub span: Span,
+}
+
+impl Spanned for Use {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum UsePath {
+    /// `use components.Hello`, resolved to `components/Hello.zac`.
+    Module(Vec<String>),
+    /// `use "./router.zac"`
+    File(String),
+}
+
+/// `import React, { useState } from "react"`, the imported names are known
+/// as extern symbols, typed when they are declared with a type.
+#[derive(Debug, Clone)]
+pub struct Import {
+    /// `import React from "react"`
+    pub default: Option<ImportName>,
+    /// `import * as path from "path"`
+    pub namespace: Option<ImportName>,
+    /// `import { Router, serve as listen: Fn<Handler, Unit> } from "./deps.jsx"`
+    pub names: Vec<ImportName>,
+    pub source: String,
+    pub span: Span,
+}
+
+impl Import {
+    /// Every name the import brings in scope.
+    pub fn bindings(&self) -> impl Iterator<Item = &ImportName> {
+        self.default
+            .iter()
+            .chain(self.namespace.iter())
+            .chain(self.names.iter())
+    }
+}
+
+impl Spanned for Import {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportName {
+    /// The name the module exports, `a` in `{ a as b }`. `None` when it is
+    /// imported as is.
+    pub exported: Option<Ident>,
+    pub id: Ident,
+    pub ty: Type,
+    pub span: Span,
+}
+
+impl Spanned for ImportName {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Record {
     pub id: Ident,
@@ -137,7 +230,10 @@ impl Spanned for RecProp {
 
 #[derive(Debug, Clone)]
 pub struct Enum {
+    /// `pub enum`, other files can use it.
+    pub public: bool,
     pub id: Ident,
+    pub variants: Vec<EnumVariant>,
     pub span: Span,
 }
 
@@ -147,9 +243,26 @@ impl Spanned for Enum {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct EnumVariant {
+    pub id: Ident,
+    /// The types of the values the variant carries, `Some(value)` has one.
+    pub fields: Vec<Type>,
+    pub span: Span,
+}
+
+impl Spanned for EnumVariant {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Mod {
     pub path: String,
+    /// `extern mod Deno { ... }`, a JS object whose functions are declared
+    /// as the fns of the mod.
+    pub external: bool,
     pub stmts: Vec<Stmt>,
     pub span: Span,
 }
@@ -162,7 +275,11 @@ impl Spanned for Mod {
 
 #[derive(Debug, Clone)]
 pub struct Let {
+    /// `pub let`, other files can use it.
+    pub public: bool,
     pub id: String,
+    /// `Type::Default` when the type is left out, `let x = 1`.
+    pub ty: Type,
     pub expr: Expr,
     pub span: Span,
 }
@@ -178,6 +295,7 @@ pub enum Expr {
     Fn(Fn),
     FnCall(FnCall),
     Binary(Box<Binary>),
+    Unary(Box<Unary>),
     LitBoolean(LitBoolean),
     LitNumber(LitNumber),
     LitString(LitString),
@@ -187,6 +305,8 @@ pub enum Expr {
     InitRecord(InitRecord),
     MemberAccess(Box<MemberAccess>),
     If(Box<If>),
+    Match(Box<Match>),
+    Variant(Variant),
 }
 
 impl From<Ident> for Expr {
@@ -213,6 +333,12 @@ impl From<Binary> for Expr {
     }
 }
 
+impl From<Unary> for Expr {
+    fn from(val: Unary) -> Self {
+        Expr::Unary(Box::new(val))
+    }
+}
+
 impl From<LitBoolean> for Expr {
     fn from(val: LitBoolean) -> Self {
         Expr::LitBoolean(val)
@@ -261,6 +387,12 @@ impl From<If> for Expr {
     }
 }
 
+impl From<Match> for Expr {
+    fn from(val: Match) -> Self {
+        Expr::Match(Box::new(val))
+    }
+}
+
 impl From<MemberAccess> for Expr {
     fn from(val: MemberAccess) -> Self {
         Expr::MemberAccess(Box::new(val))
@@ -274,6 +406,7 @@ impl Spanned for Expr {
             Expr::Fn(v) => v.span(),
             Expr::FnCall(v) => v.span(),
             Expr::Binary(v) => v.span(),
+            Expr::Unary(v) => v.span(),
             Expr::LitBoolean(v) => v.span(),
             Expr::LitNumber(v) => v.span(),
             Expr::LitString(v) => v.span(),
@@ -283,10 +416,32 @@ impl Spanned for Expr {
             Expr::InitRecord(v) => v.span(),
             Expr::MemberAccess(v) => v.span(),
             Expr::If(v) => v.span(),
+            Expr::Match(v) => v.span(),
+            Expr::Variant(v) => v.span(),
         }
     }
 }
 
+/// A variant written without its enum, like `.Some(x)` or `.None`.
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub id: Ident,
+    pub args: Vec<Expr>,
+    pub span: Span,
+}
+
+impl Spanned for Variant {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+impl From<Variant> for Expr {
+    fn from(val: Variant) -> Self {
+        Expr::Variant(val)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LitBoolean {
     pub raw: String,
@@ -317,9 +472,25 @@ impl Spanned for LitNumber {
 pub struct LitString {
     pub raw: String,
     pub value: String,
+    /// The text of the string split around its `#{...}` interpolations.
+    pub parts: Vec<StrPart>,
     pub span: Span,
 }
 
+impl LitString {
+    pub fn interpolates(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|part| matches!(part, StrPart::Expr(_)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StrPart {
+    Text(String),
+    Expr(Expr),
+}
+
 impl Spanned for LitString {
     fn span(&self) -> Span {
         self.span.clone()
@@ -371,6 +542,14 @@ impl Spanned for JsxElementAttribute {
 pub struct Fn {
     pub id: Ident,
     pub anonymous: bool,
+    /// `extern fn fetch(url: String): Promise<Response>`, a JS function
+    /// declared along with its types. It has no body.
+    pub external: bool,
+    /// `pub fn`, other files can use it.
+    pub public: bool,
+    /// The body uses `.await`, the fn is generated `async` and returns a
+    /// `Promise` of its output.
+    pub is_async: bool,
     pub args: Vec<FnArg>,
     pub stmts: Vec<FnStmt>,
     pub output: Type,
@@ -422,8 +601,30 @@ pub enum FnStmt {
     MemberAccess(MemberAccess),
     LitJs(LitJs),
     If(If),
+    Match(Match),
     Ret(Return),
+    /// Any other expression, the value of an `if` branch when it comes last.
+    Expr(Expr),
     Noop,
+    /// Placeholder for a statement that failed to parse.
+    Error(Span),
+}
+
+impl Spanned for FnStmt {
+    fn span(&self) -> Span {
+        match self {
+            FnStmt::Let(v) => v.span(),
+            FnStmt::FnCall(v) => v.span(),
+            FnStmt::MemberAccess(v) => v.span(),
+            FnStmt::LitJs(v) => v.span(),
+            FnStmt::If(v) => v.span(),
+            FnStmt::Match(v) => v.span(),
+            FnStmt::Ret(v) => v.span(),
+            FnStmt::Expr(v) => v.span(),
+            FnStmt::Noop => Span::empty(),
+            FnStmt::Error(span) => span.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -452,27 +653,150 @@ impl Spanned for Binary {
     }
 }
 
-#[derive(Debug, Clone)]
+impl Binary {
+    /// The call `left |> right` stands for, `value |> f(_, 2)` is
+    /// `f(value, 2)` and `value |> String.len` is `String.len(value)`. `None`
+    /// for the other operators, or when `right` is not a fn taking the value
+    /// once.
+    pub(crate) fn piped_call(&self) -> Option<Expr> {
+        match self.op {
+            BinaryOp::Pipe => pipe_into(&self.right, &self.left),
+            _ => None,
+        }
+    }
+}
+
+fn pipe_into(target: &Expr, value: &Expr) -> Option<Expr> {
+    let is_placeholder = |arg: &Expr| matches!(arg, Expr::Id(id) if id.string == "_");
+
+    match target {
+        Expr::Id(id) if id.string != "_" => Some(
+            FnCall {
+                id: id.clone(),
+                args: vec![value.clone()],
+                span: id.span.clone(),
+            }
+            .into(),
+        ),
+        Expr::FnCall(call) if call.args.iter().filter(|arg| is_placeholder(arg)).count() == 1 => {
+            let args = call
+                .args
+                .iter()
+                .map(|arg| match arg {
+                    arg if is_placeholder(arg) => value.clone(),
+                    arg => arg.clone(),
+                })
+                .collect();
+
+            Some(
+                FnCall {
+                    args,
+                    ..call.clone()
+                }
+                .into(),
+            )
+        }
+        Expr::MemberAccess(v) if !is_placeholder(&v.obj) => pipe_into(&v.prop, value).map(|prop| {
+            MemberAccess {
+                obj: v.obj.clone(),
+                prop,
+                span: v.span.clone(),
+            }
+            .into()
+        }),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BinaryOp {
     Add,
     Sub,
     Mul,
     Div,
     Eq,
+    Ne,
     Lt,
     Gt,
+    Le,
+    Ge,
+    And,
+    Or,
+    /// `value |> f`, lowered to a call by `Binary::piped_call`.
+    Pipe,
 }
 
 impl BinaryOp {
+    /// The operator as written in zac.
     pub(crate) fn value(&self) -> String {
         match self {
             BinaryOp::Add => "+".into(),
             BinaryOp::Sub => "-".into(),
             BinaryOp::Mul => "*".into(),
             BinaryOp::Div => "/".into(),
-            BinaryOp::Eq => "=".into(),
+            BinaryOp::Eq => "==".into(),
+            BinaryOp::Ne => "!=".into(),
             BinaryOp::Lt => "<".into(),
             BinaryOp::Gt => ">".into(),
+            BinaryOp::Le => "<=".into(),
+            BinaryOp::Ge => ">=".into(),
+            BinaryOp::And => "&&".into(),
+            BinaryOp::Or => "||".into(),
+            BinaryOp::Pipe => "|>".into(),
+        }
+    }
+
+    /// The operator in the generated JS, equality never converts types.
+    pub(crate) fn js_value(&self) -> String {
+        match self {
+            BinaryOp::Eq => "===".into(),
+            BinaryOp::Ne => "!==".into(),
+            op => op.value(),
+        }
+    }
+
+    /// How tightly the operator binds, higher binds tighter. Operators of the
+    /// same precedence group to the left.
+    pub(crate) fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Pipe => 1,
+            BinaryOp::Or => 2,
+            BinaryOp::And => 3,
+            BinaryOp::Eq | BinaryOp::Ne => 4,
+            BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge => 5,
+            BinaryOp::Add | BinaryOp::Sub => 6,
+            BinaryOp::Mul | BinaryOp::Div => 7,
+        }
+    }
+}
+
+/// The precedence of prefix operators, above every binary operator.
+pub(crate) const UNARY_PRECEDENCE: u8 = 8;
+
+#[derive(Debug, Clone)]
+pub struct Unary {
+    pub op: UnaryOp,
+    pub expr: Expr,
+    pub span: Span,
+}
+
+impl Spanned for Unary {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+impl UnaryOp {
+    pub(crate) fn value(&self) -> String {
+        match self {
+            UnaryOp::Neg => "-".into(),
+            UnaryOp::Not => "!".into(),
         }
     }
 }
@@ -503,6 +827,7 @@ impl Spanned for InitArray {
 
 #[derive(Debug, Clone)]
 pub struct InitRecord {
+    pub id: Ident,
     pub props: Vec<InitObjectProp>,
     pub span: Span,
 }
@@ -520,11 +845,25 @@ pub enum InitObjectProp {
     Spread(Ident),
 }
 
+impl Spanned for InitObjectProp {
+    fn span(&self) -> Span {
+        match self {
+            InitObjectProp::Key(id) => id.span(),
+            InitObjectProp::KeyValue(id, expr) => Span::new(id.span.from, expr.span().to),
+            // The span of the name, the dots in front are left out.
+            InitObjectProp::Spread(id) => id.span(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct If {
     pub test: Expr,
-    pub truthy: Expr,
-    pub falsy: Expr,
+    pub truthy: Block,
+    /// `None` without an `else`. An `else if` is a block holding that `If` alone.
+    pub falsy: Option<Block>,
+    /// Whether it uses `.await` outside of the fns it contains.
+    pub awaits: bool,
     pub span: Span,
 }
 
@@ -534,6 +873,83 @@ impl Spanned for If {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub expr: Expr,
+    pub arms: Vec<MatchArm>,
+    /// Whether an arm uses `.await` outside of the fns it contains.
+    pub awaits: bool,
+    pub span: Span,
+}
+
+impl Spanned for Match {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    /// An arm written `pattern => expr` is a block holding that expression alone.
+    pub body: Block,
+    pub span: Span,
+}
+
+impl Spanned for MatchArm {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// `.Some(x)`, the patterns in parentheses match the values of the variant.
+    Variant(VariantPattern),
+    /// A string, number or boolean literal.
+    Literal(Expr),
+    /// `_`, matches anything.
+    Wildcard(Span),
+    /// A name, matches anything and binds it to that name.
+    Binding(Ident),
+}
+
+impl Spanned for Pattern {
+    fn span(&self) -> Span {
+        match self {
+            Pattern::Variant(v) => v.span(),
+            Pattern::Literal(v) => v.span(),
+            Pattern::Wildcard(span) => span.clone(),
+            Pattern::Binding(v) => v.span(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VariantPattern {
+    pub id: Ident,
+    pub args: Vec<Pattern>,
+    pub span: Span,
+}
+
+impl Spanned for VariantPattern {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub stmts: Vec<FnStmt>,
+    pub span: Span,
+}
+
+impl Spanned for Block {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MemberAccess {
     pub obj: Expr,
@@ -548,7 +964,7 @@ impl Spanned for MemberAccess {
 }
 impl Ident {
     pub fn concat(&self, other: Ident) -> Ident {
-        let string = vec![self.string.clone(), other.string.clone()].join(".");
+        let string = [self.string.clone(), other.string.clone()].join(".");
 
         Ident {
             string,
@@ -0,0 +1,65 @@
+use crate::ast;
+
+use super::{context::Context, expression, types::Type};
+
+/// Checks the operands of `ast`. `+` adds Numbers or joins Strings, the other
+/// arithmetic operators take Numbers and the logical ones Booleans. A pipe is
+/// checked as the call it stands for.
+pub fn check(ctx: &mut Context, ast: &ast::Binary) -> Type {
+    if let Some(call) = ast.piped_call() {
+        return expression::check(ctx, &call);
+    }
+
+    let left = expression::check(ctx, &ast.left);
+    let right = expression::check(ctx, &ast.right);
+    let message = format!("The operator {} expects", ast.op.value());
+
+    match ast.op {
+        ast::BinaryOp::Add => {
+            let ty = left.clone().or(right.clone());
+
+            if ty.is("Number") || ty.is("String") {
+                expression::expect(ctx, &ast.left, &ty, &left, &message);
+                expression::expect(ctx, &ast.right, &ty, &right, &message);
+            } else if ty != Type::Unknown {
+                let operand = if left == Type::Unknown {
+                    &ast.right
+                } else {
+                    &ast.left
+                };
+
+                ctx.report_custom(operand, &format!("{message} Number or String, found {ty}."));
+            }
+
+            ty
+        }
+        ast::BinaryOp::Sub | ast::BinaryOp::Mul | ast::BinaryOp::Div => {
+            expression::expect(ctx, &ast.left, &Type::number(), &left, &message);
+            expression::expect(ctx, &ast.right, &Type::number(), &right, &message);
+
+            Type::number()
+        }
+        ast::BinaryOp::And | ast::BinaryOp::Or => {
+            expression::expect(ctx, &ast.left, &Type::boolean(), &left, &message);
+            expression::expect(ctx, &ast.right, &Type::boolean(), &right, &message);
+
+            Type::boolean()
+        }
+        ast::BinaryOp::Eq
+        | ast::BinaryOp::Ne
+        | ast::BinaryOp::Lt
+        | ast::BinaryOp::Gt
+        | ast::BinaryOp::Le
+        | ast::BinaryOp::Ge => {
+            if !left.accepts(&right) {
+                ctx.report_custom(
+                    ast,
+                    &format!("Values of type {left} and {right} cannot be compared."),
+                );
+            }
+
+            Type::boolean()
+        }
+        ast::BinaryOp::Pipe => Type::Unknown,
+    }
+}
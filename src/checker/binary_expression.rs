@@ -1,5 +0,0 @@
-use crate::{ast::AST, scope::Scope};
-
-use super::context::CheckingContext;
-
-pub fn check(ctx: &CheckingContext, scope: &mut Scope, ast: &AST) {}
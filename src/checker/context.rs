@@ -1,33 +1,208 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 
-use crate::{error_message::ErrorMessage, scope::Scope};
+use crate::{
+    ast,
+    diagnostic::{code, Diagnostic},
+    loader::UsedFiles,
+    span::Spanned,
+    Target,
+};
 
-pub struct CheckingContext {
-    pub filepath: String,
-    pub content: String,
-    pub scopes: HashMap<String, Box<Scope>>,
+use super::{types::Type, Methods};
+
+pub struct Context {
+    pub file_path: String,
+    /// How the generated code gets loaded, scripts cannot await at the top
+    /// level.
+    pub target: Target,
+    pub module_path: String,
+    pub mod_defs: HashMap<String, ast::Mod>,
+    /// The fns by the name they are generated with, `Mod_fn` inside a mod.
+    pub fn_defs: HashMap<String, ast::Fn>,
+    pub record_defs: HashMap<String, ast::Record>,
+    pub enum_defs: HashMap<String, ast::Enum>,
+    /// The definitions of used files that are not pub, by the name they are
+    /// registered with, along with the file they are declared in.
+    pub private_defs: HashMap<String, String>,
+    /// The files the definitions of used files are declared in, by the name
+    /// they are registered with.
+    pub def_files: HashMap<String, String>,
+    /// The types of the variables and arguments in scope.
+    pub var_types: HashMap<String, Type>,
+    /// The fn whose body is being checked, `None` at the top level.
+    pub current_fn: Option<ast::Fn>,
+    /// The type parameters of the fns being checked, a lambda can use the
+    /// ones of the fn around it.
+    pub type_params: Vec<ast::Ident>,
+    /// The output the values returned by the current fn are checked against,
+    /// declared or taken from the fn type a lambda is passed as.
+    pub output: Type,
+    /// The type of the first value of known type the current fn returns.
+    pub returned: Type,
+    pub diagnostics: Vec<Diagnostic>,
+    /// The fns the `value.name()` calls checked so far resolve to.
+    pub methods: Methods,
+    pub used_files: Rc<UsedFiles>,
 }
 
-impl CheckingContext {
-    pub fn print_error_message(&self, message: String, pos: usize) {
-        let filepath = self.filepath.clone();
-        let context = self.content.clone();
-        ErrorMessage::new(filepath, context, message, pos).print();
+impl Context {
+    pub fn new(file_path: String) -> Context {
+        Context {
+            file_path,
+            target: Target::Module,
+            module_path: "".into(),
+            mod_defs: HashMap::new(),
+            fn_defs: HashMap::new(),
+            record_defs: HashMap::new(),
+            enum_defs: HashMap::new(),
+            private_defs: HashMap::new(),
+            def_files: HashMap::new(),
+            var_types: HashMap::new(),
+            current_fn: None,
+            type_params: vec![],
+            output: Type::Unknown,
+            returned: Type::Unknown,
+            diagnostics: vec![],
+            methods: Methods::new(),
+            used_files: Rc::new(UsedFiles::new()),
+        }
     }
 
-    pub fn add_scope(&mut self, identifier: &String, scope: Scope) {
-        self.scopes.insert(identifier.clone(), Box::new(scope));
+    pub fn error(&self, spanned: &dyn Spanned, message: &str) -> Diagnostic {
+        Diagnostic::error(self.file_path.clone(), message.to_string(), spanned.span())
+            .with_code(code::TYPE_MISMATCH)
     }
 
-    pub fn get_scope(&self, identifier: &String) -> Option<&Box<Scope>> {
-        self.scopes.get(identifier)
+    /// Keeps `diagnostic` and carries on, so every mismatch of the file gets
+    /// reported at once.
+    pub fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
     }
-}
 
-pub fn new(filename: &String, content: &String) -> CheckingContext {
-    CheckingContext {
-        filepath: filename.clone(),
-        content: content.clone(),
-        scopes: HashMap::new(),
+    pub fn report_custom(&mut self, spanned: &dyn Spanned, message: &str) {
+        let diagnostic = self.error(spanned, message);
+
+        self.report(diagnostic);
+    }
+
+    /// Reports the use of `path` when another file keeps it to itself, `name`
+    /// is how the definition reads in the message.
+    pub fn check_public(&mut self, spanned: &dyn Spanned, path: &str, name: &str) {
+        let Some(file_path) = self.private_defs.get(path) else {
+            return;
+        };
+
+        let diagnostic = self
+            .error(
+                spanned,
+                &format!("{name} is not pub in {file_path}, other files cannot use it."),
+            )
+            .with_code(code::PRIVATE);
+
+        self.report(diagnostic);
+    }
+
+    /// Adds a fn of the file, it replaces the one of a used file.
+    pub fn add_fn(&mut self, path: String, fn_def: ast::Fn) {
+        self.private_defs.remove(&path);
+        self.def_files.remove(&path);
+        self.fn_defs.insert(path, fn_def);
+    }
+
+    /// The fn `name` refers to from the current mod along with the name it is
+    /// registered with, its use is reported when another file keeps it to
+    /// itself.
+    pub fn find_fn(&mut self, spanned: &dyn Spanned, name: &str) -> Option<(String, ast::Fn)> {
+        let path = name.replace('.', "_");
+        let path = [self.fn_path(&path), path]
+            .into_iter()
+            .find(|path| self.fn_defs.contains_key(path))?;
+
+        self.check_public(spanned, &path, &format!("The function {name}"));
+        self.fn_defs
+            .get(&path)
+            .cloned()
+            .map(|fn_def| (path, fn_def))
+    }
+
+    /// The file the definition registered as `path` is declared in.
+    pub fn def_file(&self, path: &str) -> String {
+        self.def_files
+            .get(path)
+            .cloned()
+            .unwrap_or_else(|| self.file_path.clone())
+    }
+
+    /// Runs `check` in a scope of its own, the variables it declares are gone
+    /// once it returns.
+    pub fn scoped<T>(&mut self, check: impl FnOnce(&mut Self) -> T) -> T {
+        let var_types = self.var_types.clone();
+        let current_fn = self.current_fn.clone();
+        let type_params = self.type_params.clone();
+        let output = self.output.clone();
+        let returned = self.returned.clone();
+        let result = check(self);
+
+        self.var_types = var_types;
+        self.current_fn = current_fn;
+        self.type_params = type_params;
+        self.output = output;
+        self.returned = returned;
+
+        result
+    }
+
+    /// The type written as `ty` where the type parameters in scope are known.
+    pub fn resolve_type(&self, ty: &ast::Type) -> Type {
+        Type::from(ty).with_params(&self.type_params)
+    }
+
+    /// The name a fn declared in the current mod is generated with.
+    pub fn fn_path(&self, name: &str) -> String {
+        if self.module_path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}_{name}", self.module_path)
+        }
+    }
+
+    /// Whether `path` names a mod, or the start of the path of one.
+    pub fn is_mod(&self, path: &str) -> bool {
+        self.mod_defs
+            .keys()
+            .any(|mod_path| mod_path == path || mod_path.starts_with(&format!("{path}.")))
+    }
+
+    /// The fn named `name` that takes a value of type `ty` first, like the
+    /// generator resolves `value.name()`. A fn declared for `ty` itself wins
+    /// over one taking any value.
+    pub fn find_method(&self, name: &str, ty: &Type) -> Option<(String, ast::Fn)> {
+        let mut paths = self.fn_defs.keys().collect::<Vec<_>>();
+
+        paths.sort();
+
+        let methods = paths
+            .into_iter()
+            .filter_map(|path| self.fn_defs.get_key_value(path))
+            .filter(|(_, fn_def)| fn_def.id.string == name)
+            .filter_map(
+                |(path, fn_def)| match fn_def.args.first().map(|arg| &arg.input) {
+                    Some(input @ ast::Type::Ident(_)) => {
+                        let input = Type::from(input)
+                            .with_params(&fn_def.id.generics)
+                            .substitute(&HashMap::new());
+
+                        input.accepts(ty).then_some((input, path, fn_def))
+                    }
+                    _ => None,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        methods
+            .iter()
+            .find(|(input, _, _)| input != &Type::Unknown)
+            .or(methods.first())
+            .map(|(_, path, fn_def)| ((*path).clone(), (*fn_def).clone()))
     }
 }
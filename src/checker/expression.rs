@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use crate::ast;
+
+use super::{
+    binary, context::Context, fn_call, init_array, init_record, jsx_element, member_access,
+    statement_fn, statement_if, statement_match, types::Type, unary, variant,
+};
+
+/// Checks `ast` and returns its type.
+pub fn check(ctx: &mut Context, ast: &ast::Expr) -> Type {
+    match ast {
+        ast::Expr::Id(v) if v.string == "_" => {
+            ctx.report_custom(
+                v,
+                "`_` only stands for the piped value, as one argument of the function after `|>`.",
+            );
+            Type::Unknown
+        }
+        ast::Expr::Id(v) => match ctx.var_types.get(&v.string) {
+            Some(ty) => ty.clone(),
+            None => fn_type(ctx, v).unwrap_or_else(|| {
+                let name = format!("The variable {}", v.string);
+
+                ctx.check_public(v, &v.string, &name);
+                Type::Unknown
+            }),
+        },
+        ast::Expr::Fn(v) => statement_fn::check(ctx, v),
+        ast::Expr::FnCall(v) => fn_call::check(ctx, v),
+        ast::Expr::Binary(v) => binary::check(ctx, v),
+        ast::Expr::Unary(v) => unary::check(ctx, v),
+        ast::Expr::LitBoolean(_) => Type::boolean(),
+        ast::Expr::LitNumber(_) => Type::number(),
+        ast::Expr::LitString(v) => {
+            for part in &v.parts {
+                if let ast::StrPart::Expr(expr) = part {
+                    check(ctx, expr);
+                }
+            }
+
+            Type::string()
+        }
+        ast::Expr::LitJs(_) => Type::Unknown,
+        ast::Expr::JsxElement(v) => jsx_element::check(ctx, v),
+        ast::Expr::InitArray(v) => init_array::check(ctx, v),
+        ast::Expr::InitRecord(v) => init_record::check(ctx, v),
+        ast::Expr::MemberAccess(v) => member_access::check(ctx, v),
+        ast::Expr::If(v) => statement_if::check(ctx, v),
+        ast::Expr::Match(v) => statement_match::check(ctx, v),
+        ast::Expr::Variant(v) => variant::check(ctx, v),
+    }
+}
+
+/// Checks `ast` where a value of type `expected` is expected and returns its
+/// type, a lambda takes the types of its arguments from `expected`.
+pub fn check_expected(ctx: &mut Context, ast: &ast::Expr, expected: &Type) -> Type {
+    match ast {
+        ast::Expr::Fn(v) => statement_fn::check_expected(ctx, v, expected),
+        ast => check(ctx, ast),
+    }
+}
+
+/// The type of the fn named `id` when it is passed as a value, its type
+/// parameters fit anything.
+pub fn fn_type(ctx: &mut Context, id: &ast::Ident) -> Option<Type> {
+    let (_, fn_def) = ctx.find_fn(id, &id.string)?;
+
+    let signature = |ty: &ast::Type| {
+        Type::from(ty)
+            .with_params(&fn_def.id.generics)
+            .substitute(&HashMap::new())
+    };
+
+    let output = if fn_def.is_async {
+        signature(&fn_def.output).promised()
+    } else {
+        signature(&fn_def.output)
+    };
+
+    Some(Type::Fn(
+        fn_def
+            .args
+            .iter()
+            .map(|arg| signature(&arg.input))
+            .collect(),
+        Box::new(output),
+    ))
+}
+
+/// Reports `found` when it does not fit `expected`, with `message` in front.
+pub fn expect(ctx: &mut Context, ast: &ast::Expr, expected: &Type, found: &Type, message: &str) {
+    if !expected.accepts(found) {
+        ctx.report_custom(ast, &format!("{message} {expected}, found {found}."));
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crate::ast;
+
+use super::{context::Context, expression, types::Type};
+
+/// Checks a call to a fn of this file or of a used one, or to a variable
+/// holding a fn, calls to anything else are not known and return a value of
+/// unknown type.
+pub fn check(ctx: &mut Context, ast: &ast::FnCall) -> Type {
+    let fn_def = if let Some(ty) = ctx.var_types.get(&ast.id.string) {
+        if let Type::Fn(inputs, output) = ty.clone() {
+            return check_fn_type(ctx, &inputs, *output, ast);
+        }
+
+        None
+    } else {
+        ctx.find_fn(ast, &ast.id.string)
+    };
+
+    match fn_def {
+        Some((fn_path, fn_def)) => check_args(ctx, &fn_path, &fn_def, ast, None),
+        None => check_unknown(ctx, ast),
+    }
+}
+
+/// Checks the arguments of `ast` against the ones of `fn_def`, registered as
+/// `fn_path`, after the
+/// first one when `receiver` is the value a method is called on. The type
+/// parameters of the fn take the types of the values given for them, and
+/// the output of the fn is returned with them in place.
+pub fn check_args(
+    ctx: &mut Context,
+    fn_path: &str,
+    fn_def: &ast::Fn,
+    ast: &ast::FnCall,
+    receiver: Option<&Type>,
+) -> Type {
+    let name = &fn_def.id.string;
+    let fn_file = ctx.def_file(fn_path);
+    let signature = |ty: &ast::Type| Type::from(ty).with_params(&fn_def.id.generics);
+    let mut bindings = HashMap::new();
+
+    let inputs = match (receiver, fn_def.args.split_first()) {
+        (Some(receiver), Some((first, rest))) => {
+            signature(&first.input).unify(receiver, &mut bindings);
+            rest
+        }
+        _ => fn_def.args.as_slice(),
+    };
+
+    if inputs.len() != ast.args.len() {
+        let error = ctx
+            .error(
+                ast,
+                &format!(
+                    "The function {name} takes {} argument(s) but {} were given.",
+                    inputs.len(),
+                    ast.args.len()
+                ),
+            )
+            .with_note_in(
+                &fn_file,
+                &format!("The function {name} is declared here."),
+                Some(fn_def.span.clone()),
+            );
+
+        ctx.report(error);
+    }
+
+    for (idx, arg) in ast.args.iter().enumerate() {
+        let Some(input) = inputs.get(idx) else {
+            expression::check(ctx, arg);
+            continue;
+        };
+
+        let expected = signature(&input.input);
+        let found = expression::check_expected(ctx, arg, &expected.substitute(&bindings));
+
+        expected.unify(&found, &mut bindings);
+
+        let expected = expected.substitute(&bindings);
+
+        if !expected.accepts(&found) {
+            let error = ctx
+                .error(
+                    arg,
+                    &format!(
+                        "The argument {} of {name} expects {expected}, found {found}.",
+                        input.id.string
+                    ),
+                )
+                .with_note_in(
+                    &fn_file,
+                    &format!("The function {name} is declared here."),
+                    Some(fn_def.span.clone()),
+                );
+
+            ctx.report(error);
+        }
+    }
+
+    let output = signature(&fn_def.output).substitute(&bindings);
+
+    if fn_def.is_async {
+        output.promised()
+    } else {
+        output
+    }
+}
+
+/// Checks the arguments of a call to a variable of type `Fn<inputs..., output>`.
+fn check_fn_type(ctx: &mut Context, inputs: &[Type], output: Type, ast: &ast::FnCall) -> Type {
+    let name = &ast.id.string;
+
+    if inputs.len() != ast.args.len() {
+        ctx.report_custom(
+            ast,
+            &format!(
+                "The function {name} takes {} argument(s) but {} were given.",
+                inputs.len(),
+                ast.args.len()
+            ),
+        );
+    }
+
+    for (idx, arg) in ast.args.iter().enumerate() {
+        let expected = inputs.get(idx).cloned().unwrap_or(Type::Unknown);
+        let found = expression::check_expected(ctx, arg, &expected);
+
+        expression::expect(
+            ctx,
+            arg,
+            &expected,
+            &found,
+            &format!("The argument {} of {name} expects", idx + 1),
+        );
+    }
+
+    output
+}
+
+/// Checks the arguments given to a fn that is not known.
+pub fn check_unknown(ctx: &mut Context, ast: &ast::FnCall) -> Type {
+    for arg in &ast.args {
+        expression::check(ctx, arg);
+    }
+
+    Type::Unknown
+}
@@ -0,0 +1,15 @@
+use crate::ast;
+
+use super::{context::Context, expression, types::Type};
+
+/// Checks the items of an array, its type is `Array` of the first item whose
+/// type is known.
+pub fn check(ctx: &mut Context, ast: &ast::InitArray) -> Type {
+    let item = ast
+        .items
+        .iter()
+        .map(|item| expression::check(ctx, item))
+        .fold(Type::Unknown, Type::or);
+
+    Type::Named("Array".into(), vec![item])
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use crate::ast;
+
+use super::{context::Context, expression, types::Type};
+
+/// Checks the fields against the record declaration, and their values against
+/// the declared types. Fields can only be left out when another record is
+/// spread in.
+pub fn check(ctx: &mut Context, ast: &ast::InitRecord) -> Type {
+    let name = &ast.id.string;
+    let record = ctx.record_defs.get(name).cloned();
+
+    if record.is_none() {
+        ctx.report_custom(&ast.id, &format!("The record {name} is not defined."));
+    }
+
+    let mut fields: Vec<&ast::Ident> = vec![];
+    let mut has_spread = false;
+
+    for prop in &ast.props {
+        let (id, found, expr) = match prop {
+            ast::InitObjectProp::KeyValue(id, expr) => (id, expression::check(ctx, expr), expr),
+            ast::InitObjectProp::Key(id) => (
+                id,
+                ctx.var_types
+                    .get(&id.string)
+                    .cloned()
+                    .unwrap_or(Type::Unknown),
+                &ast::Expr::Id(id.clone()),
+            ),
+            ast::InitObjectProp::Spread(_) => {
+                has_spread = true;
+                continue;
+            }
+        };
+
+        let Some(record) = &record else {
+            continue;
+        };
+
+        if let Some(previous) = fields.iter().find(|field| field.string == id.string) {
+            let diagnostic = ctx
+                .error(
+                    id,
+                    &format!("The field {} is set more than once.", id.string),
+                )
+                .with_note("It is first set here.", Some(previous.span.clone()));
+            ctx.report(diagnostic);
+            continue;
+        }
+
+        fields.push(id);
+
+        let Some(field) = record
+            .props
+            .iter()
+            .find(|field| field.id.string == id.string)
+        else {
+            let diagnostic = ctx
+                .error(
+                    id,
+                    &format!("The record {name} has no field {}.", id.string),
+                )
+                .with_note_in(
+                    &ctx.def_file(name),
+                    &format!("The record {name} is declared here."),
+                    Some(record.span.clone()),
+                );
+            ctx.report(diagnostic);
+            continue;
+        };
+
+        // The type parameters of the record fit any value.
+        let expected = Type::from(&field.init)
+            .with_params(&record.id.generics)
+            .substitute(&HashMap::new());
+        let message = format!("The field {} of {name} expects", id.string);
+
+        expression::expect(ctx, expr, &expected, &found, &message);
+    }
+
+    if let Some(record) = &record {
+        let missing = record
+            .props
+            .iter()
+            .filter(|prop| !fields.iter().any(|field| field.string == prop.id.string))
+            .map(|prop| prop.id.string.clone())
+            .collect::<Vec<_>>();
+
+        if !has_spread && !missing.is_empty() {
+            let diagnostic = ctx
+                .error(
+                    ast,
+                    &format!(
+                        "The record {name} is missing the field(s) {}.",
+                        missing.join(", ")
+                    ),
+                )
+                .with_note_in(
+                    &ctx.def_file(name),
+                    &format!("The record {name} is declared here."),
+                    Some(record.span.clone()),
+                );
+            ctx.report(diagnostic);
+        }
+    }
+
+    Type::named(name)
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use crate::{ast, Target};
+
+use super::{context::Context, expression, fn_call, types::Type, variant};
+
+/// Checks a chain like `Fetch.new(url).await.json()` the way the generator
+/// resolves it. Names of mods lead to the fns declared in them and
+/// `value.name()` calls the fn `name` taking that value first.
+pub fn check(ctx: &mut Context, ast: &ast::MemberAccess) -> Type {
+    if let ast::Expr::Id(obj) = &ast.obj {
+        if let Some(enum_def) = ctx.enum_defs.get(&obj.string).cloned() {
+            match &ast.prop {
+                ast::Expr::Id(v) => return variant::check_qualified(ctx, &enum_def, v, &[], ast),
+                ast::Expr::FnCall(v) => {
+                    return variant::check_qualified(ctx, &enum_def, &v.id, &v.args, ast)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut items = vec![&ast.obj];
+
+    flatten(&ast.prop, &mut items);
+
+    // The mods walked so far, `None` once the chain holds a value.
+    let mut path: Option<Vec<String>> = Some(vec![]);
+    let mut ty = Type::Unknown;
+
+    for (idx, item) in items.into_iter().enumerate() {
+        ty = match (item, &mut path) {
+            (ast::Expr::Id(v), _) if idx > 0 && v.string == "await" => {
+                if ctx.current_fn.is_none() && ctx.target == Target::Script {
+                    ctx.report_custom(
+                        v,
+                        "Top-level .await is only allowed when the target is an ES module.",
+                    );
+                }
+
+                ty.awaited()
+            }
+            (ast::Expr::Id(v), Some(path))
+                if !(idx == 0 && ctx.var_types.contains_key(&v.string))
+                    && ctx.is_mod(&mod_path(path, &v.string)) =>
+            {
+                path.push(v.string.clone());
+                continue;
+            }
+            (ast::Expr::FnCall(v), Some(path)) if !path.is_empty() => {
+                let fn_path = format!("{}_{}", path.join("_"), v.id.string.replace('.', "_"));
+
+                match ctx.fn_defs.get(&fn_path).cloned() {
+                    Some(fn_def) => {
+                        let name = format!("The function {}.{}", path.join("."), v.id.string);
+
+                        ctx.check_public(v, &fn_path, &name);
+                        fn_call::check_args(ctx, &fn_path, &fn_def, v, None)
+                    }
+                    None => fn_call::check_unknown(ctx, v),
+                }
+            }
+            (ast::Expr::FnCall(v), None) => match ctx.find_method(&v.id.string, &ty) {
+                Some((fn_path, fn_def)) if ty != Type::Unknown => {
+                    ctx.check_public(v, &fn_path, &format!("The function {}", v.id.string));
+                    ctx.methods.insert(v.span.clone(), fn_path.clone());
+                    fn_call::check_args(ctx, &fn_path, &fn_def, v, Some(&ty))
+                }
+                _ => fn_call::check_unknown(ctx, v),
+            },
+            (ast::Expr::Id(v), Some(path)) if !path.is_empty() => {
+                let var_path = format!("{}_{}", path.join("_"), v.string);
+                let name = mod_path(path, &v.string);
+
+                match ctx.var_types.get(&var_path).cloned() {
+                    Some(ty) => {
+                        ctx.check_public(v, &var_path, &format!("The variable {name}"));
+                        ty
+                    }
+                    // A fn passed as a value, `apply(String.len, s)`.
+                    None => expression::fn_type(
+                        ctx,
+                        &ast::Ident {
+                            string: name.clone(),
+                            ..v.clone()
+                        },
+                    )
+                    .unwrap_or_else(|| {
+                        ctx.report_custom(
+                            v,
+                            &format!(
+                                "The mod {} has no function or variable {}.",
+                                path.join("."),
+                                v.string
+                            ),
+                        );
+                        Type::Unknown
+                    }),
+                }
+            }
+            (ast::Expr::Id(v), _) if idx > 0 => field(ctx, &ty, v),
+            (item, _) => expression::check(ctx, item),
+        };
+
+        path = None;
+    }
+
+    ty
+}
+
+/// The type of the field `id` of a value of type `ty`, only known when it is a
+/// record.
+fn field(ctx: &mut Context, ty: &Type, id: &ast::Ident) -> Type {
+    let Type::Named(name, args) = ty else {
+        return Type::Unknown;
+    };
+    let Some(record) = ctx.record_defs.get(name).cloned() else {
+        return Type::Unknown;
+    };
+
+    match record
+        .props
+        .iter()
+        .find(|field| field.id.string == id.string)
+    {
+        Some(field) => {
+            let bindings = record
+                .id
+                .generics
+                .iter()
+                .map(|param| param.string.clone())
+                .zip(args.iter().cloned())
+                .collect::<HashMap<_, _>>();
+
+            Type::from(&field.init)
+                .with_params(&record.id.generics)
+                .substitute(&bindings)
+        }
+        None => {
+            let diagnostic = ctx
+                .error(
+                    id,
+                    &format!("The record {name} has no field {}.", id.string),
+                )
+                .with_note_in(
+                    &ctx.def_file(name),
+                    &format!("The record {name} is declared here."),
+                    Some(record.span.clone()),
+                );
+            ctx.report(diagnostic);
+
+            Type::Unknown
+        }
+    }
+}
+
+fn flatten<'a>(prop: &'a ast::Expr, items: &mut Vec<&'a ast::Expr>) {
+    match prop {
+        ast::Expr::MemberAccess(v) => {
+            items.push(&v.obj);
+            flatten(&v.prop, items);
+        }
+        prop => items.push(prop),
+    }
+}
+
+/// The path of the mod `name` inside the mods `path`, `Express.Router`.
+fn mod_path(path: &[String], name: &str) -> String {
+    path.iter()
+        .map(String::as_str)
+        .chain([name])
+        .collect::<Vec<_>>()
+        .join(".")
+}
@@ -1,25 +1,58 @@
-mod array_declarator;
-mod binary_expression;
-pub mod context;
-mod enum_definition;
-mod function_call;
-mod function_definition;
-mod identifier;
-mod if_statement;
+mod binary;
+mod context;
+mod expression;
+mod fn_call;
+mod init_array;
+mod init_record;
 mod jsx_element;
-mod literal;
-mod member_expression;
-mod record_definition;
+mod member_access;
 mod root;
-mod statement;
-mod type_definition;
-mod type_resolver;
-mod variable_declaration;
-mod return_statement;
+mod statement_fn;
+mod statement_if;
+mod statement_import;
+mod statement_let;
+mod statement_match;
+mod statement_mod;
+mod statement_return;
+mod statement_use;
+mod types;
+mod unary;
+mod variant;
 
-use crate::ast::AST;
+use std::{collections::HashMap, rc::Rc};
 
-pub fn check(filepath: &String, content: &String, ast: &AST) {
-    let mut context = context::new(filepath, content);
-    root::check(&mut context, ast.clone());
+use crate::{ast, diagnostic::Diagnostic, loader::UsedFiles, span::Span, Target};
+
+/// The fns `value.name()` calls resolve to, by the span of the call. The
+/// generator cannot tell the type of every value on its own.
+pub type Methods = HashMap<Span, String>;
+
+/// Checks the types of `root_ast`, reporting every mismatch in the file rather
+/// than stopping at the first one. The definitions of `prelude` are known
+/// unless the file declares its own. The methods the calls of the file resolve
+/// to are returned for the generator. `used_files` are the files the `use`
+/// statements of the file refer to.
+pub fn check(
+    file_path: &str,
+    root_ast: &ast::Root,
+    prelude: Option<&ast::Root>,
+    target: Target,
+    used_files: Rc<UsedFiles>,
+) -> Result<Methods, Vec<Diagnostic>> {
+    let mut ctx = context::Context::new(file_path.to_string());
+
+    ctx.target = target;
+    ctx.used_files = used_files;
+
+    if let Some(prelude) = prelude {
+        statement_use::register_file(&mut ctx, statement_use::unlocated(prelude.clone()));
+    }
+
+    root::check(&mut ctx, root_ast);
+
+    if ctx.diagnostics.is_empty() {
+        Ok(ctx.methods)
+    } else {
+        Err(ctx.diagnostics)
+    }
 }
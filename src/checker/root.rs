@@ -1,144 +1,73 @@
-use crate::{
-    ast::ASTKind,
-    ast::AST,
-    scope::{self, Scope},
-};
+use crate::ast;
 
 use super::{
-    array_declarator, context::CheckingContext, enum_definition, function_call,
-    function_definition, member_expression, record_definition, type_definition,
-    variable_declaration,
+    context::Context, fn_call, member_access, statement_fn, statement_if, statement_import,
+    statement_let, statement_match, statement_mod, statement_return, statement_use,
 };
 
-pub fn check(ctx: &mut CheckingContext, ast: AST) {
-    let mut global_scope = scope::new();
+pub fn check(ctx: &mut Context, root_ast: &ast::Root) {
+    // Fns, records, enums and imports can be used before they are declared.
+    register(ctx, &root_ast.stmts);
 
-    match ast {
-        AST::Root { children } => {
-            check_children(ctx, &mut global_scope, &children);
-        }
-        _ => {
-            panic!(
-                "Unexpected '{:?}', expecting '{:?}'",
-                ASTKind::from(ast),
-                ASTKind::Root
-            )
+    for stmt in &root_ast.stmts {
+        match stmt {
+            ast::Stmt::Mod(v) => statement_mod::check(ctx, v),
+            ast::Stmt::Let(v) => statement_let::check(ctx, v),
+            ast::Stmt::Fn(v) => {
+                statement_fn::check(ctx, v);
+            }
+            ast::Stmt::FnCall(v) => {
+                fn_call::check(ctx, v);
+            }
+            ast::Stmt::MemberAccess(v) => {
+                member_access::check(ctx, v);
+            }
+            ast::Stmt::Return(v) => statement_return::check(ctx, v),
+            ast::Stmt::If(v) => {
+                statement_if::check(ctx, v);
+            }
+            ast::Stmt::Match(v) => {
+                statement_match::check(ctx, v);
+            }
+            ast::Stmt::Use(_)
+            | ast::Stmt::Import(_)
+            | ast::Stmt::Enum(_)
+            | ast::Stmt::Record(_)
+            | ast::Stmt::LitJs(_)
+            | ast::Stmt::Noop
+            | ast::Stmt::Error(_) => {}
         }
     }
 }
 
-pub fn check_children(ctx: &mut CheckingContext, scope: &mut Scope, body: &Vec<AST>) {
-    let mut children_iter = body.iter();
+/// Adds the fns, mods, records, enums and imports among `stmts` to `ctx`,
+/// along with the ones of the files they use. The used files come first
+/// wherever they are used, the definitions of the file replace theirs.
+pub fn register(ctx: &mut Context, stmts: &[ast::Stmt]) {
+    for stmt in stmts {
+        match stmt {
+            ast::Stmt::Use(v) => statement_use::register(ctx, v),
+            ast::Stmt::Import(v) => statement_import::register(ctx, v),
+            _ => {}
+        }
+    }
 
-    while let Some(child) = children_iter.next() {
-        match child {
-            AST::Root { children: _ } => {}
-            AST::NumberLiteral { value: _, span: _ } => {}
-            AST::StringLiteral { value: _, span: _ } => {}
-            AST::Identifier {
-                value: _,
-                generics: _,
-                span: _,
-            } => {}
-            AST::JsLiteral { value: _, span: _ } => {}
-            AST::JsxElement {
-                name: _,
-                attrs: _,
-                children: _,
-                self_closing: _,
-                span: _,
-            } => {}
-            AST::JsxElementAttribute {
-                name: _,
-                expr: _,
-                span: _,
-            } => {}
-            AST::TypeDefinition {
-                name,
-                generics: _,
-                variants: _,
-                span: _,
-            } => {
-                type_definition::check(ctx, scope, child);
-                scope.add_type_definition(&name, child);
-            }
-            AST::EnumDefinition {
-                name,
-                generics: _,
-                items: _,
-                span: _,
-            } => {
-                enum_definition::check(ctx, scope, child);
-                scope.add_enum_definition(&name, child);
-            }
-            AST::RecordDefinition {
-                name,
-                keys: _,
-                span: _,
-            } => {
-                record_definition::check(ctx, scope, child);
-                scope.add_record_definition(&name, child);
-            }
-            AST::RecordKeyDefinition {
-                name: _,
-                kind: _,
-                span: _,
-            } => {}
-            AST::VariableStatement {
-                name,
-                value: _,
-                span: _,
-            } => {
-                variable_declaration::check(ctx, scope, child);
-                scope.add_variable_definition(&name, child);
-            }
-            AST::ArrayDeclarator { items: _, span: _ } => {
-                array_declarator::check(ctx, scope, child);
+    for stmt in stmts {
+        match stmt {
+            ast::Stmt::Mod(v) => statement_mod::register(ctx, v),
+            ast::Stmt::Fn(v) => {
+                ctx.add_fn(ctx.fn_path(&v.id.string), v.clone());
             }
-            AST::FunctionDefinition {
-                name: _,
-                expected_return_type: _,
-                args: _,
-                body: _,
-                span: _,
-            } => {
-                function_definition::check(ctx, scope, child);
+            ast::Stmt::Record(v) => {
+                ctx.def_files.remove(&v.id.string);
+                ctx.record_defs.insert(v.id.string.clone(), v.clone());
             }
-            AST::FunctionArgumentDefinition {
-                name: _,
-                type_: _,
-                span: _,
-            } => {}
-            AST::BinaryExpression {
-                kind,
-                left,
-                right,
-                span,
-            } => {}
-            AST::FunctionCall {
-                callee: _,
-                args: _,
-                span: _,
-            } => {
-                function_call::check(ctx, scope, child);
+            ast::Stmt::Enum(v) => {
+                ctx.private_defs.remove(&v.id.string);
+                ctx.def_files.remove(&v.id.string);
+                ctx.enum_defs.insert(v.id.string.clone(), v.clone());
             }
-            AST::IfStatement {
-                test: _,
-                consequence: _,
-                alternative: _,
-                span: _,
-            } => {}
-            AST::MemberExpression {
-                object,
-                property,
-                span,
-            } => member_expression::check(ctx, scope, child),
-            AST::TypeVariant {
-                name,
-                generics,
-                span,
-            } => {}
-            AST::ReturnStatement { expr, span } => todo!(),
+            _ => {}
         }
     }
 }
@@ -0,0 +1,184 @@
+use crate::ast;
+
+use super::{
+    context::Context, expression, fn_call, member_access, statement_if, statement_let,
+    statement_match, statement_return, types::Type,
+};
+
+/// Checks the body of a fn against its arguments and declared output, and
+/// returns the type of the fn.
+pub fn check(ctx: &mut Context, ast: &ast::Fn) -> Type {
+    check_expected(ctx, ast, &Type::Unknown)
+}
+
+/// Checks a fn used where a value of type `expected` is expected. A lambda
+/// passed as a `Fn<Context, Unit>` gets the types it leaves out from it.
+pub fn check_expected(ctx: &mut Context, ast: &ast::Fn, expected: &Type) -> Type {
+    let (inputs, output) = match expected {
+        Type::Fn(inputs, output) if ast.anonymous && inputs.len() == ast.args.len() => {
+            (inputs.clone(), *output.clone())
+        }
+        Type::Fn(inputs, _) if ast.anonymous => {
+            ctx.report_custom(
+                ast,
+                &format!(
+                    "This function takes {} argument(s) but {expected} takes {}.",
+                    ast.args.len(),
+                    inputs.len()
+                ),
+            );
+            check(ctx, ast);
+
+            // Already reported, the caller should not report it again.
+            return Type::Unknown;
+        }
+        _ => (vec![], Type::Unknown),
+    };
+
+    ctx.scoped(|ctx| {
+        ctx.type_params.extend(ast.id.generics.iter().cloned());
+
+        let mut args = vec![];
+
+        for (idx, arg) in ast.args.iter().enumerate() {
+            check_params(ctx, ast, &arg.input);
+
+            let ty = ctx
+                .resolve_type(&arg.input)
+                .or(inputs.get(idx).cloned().unwrap_or(Type::Unknown));
+
+            ctx.var_types.insert(arg.id.string.clone(), ty.clone());
+            args.push(ty);
+        }
+
+        check_params(ctx, ast, &ast.output);
+
+        ctx.current_fn = Some(ast.clone());
+        ctx.output = ctx.resolve_type(&ast.output).or(output);
+        ctx.returned = Type::Unknown;
+
+        // An async fn returns the values its `Promise` resolves to.
+        if ast.is_async && ctx.output.is("Promise") {
+            ctx.output = ctx.output.awaited();
+        }
+
+        check_body(ctx, &ast.stmts);
+
+        if let ast::Type::Ident(declared) = &ast.output {
+            if !ast.external && !ctx.output.is("Unit") && !returns(&ast.stmts) {
+                check_return(ctx, ast, declared);
+            }
+        }
+
+        let output = ctx.output.clone().or(ctx.returned.clone());
+
+        if ast.is_async {
+            Type::Fn(args, Box::new(output.promised()))
+        } else {
+            Type::Fn(args, Box::new(output))
+        }
+    })
+}
+
+/// Reports a fn declaring an output it can end without returning.
+fn check_return(ctx: &mut Context, ast: &ast::Fn, declared: &ast::Ident) {
+    let output = ctx.output.clone();
+    let error = if ast.anonymous {
+        ctx.error(
+            ast,
+            &format!("This function returns {output} but can end without returning a value."),
+        )
+    } else {
+        ctx.error(
+            &ast.id,
+            &format!(
+                "The function {} returns {output} but can end without returning a value.",
+                ast.id.string
+            ),
+        )
+    };
+
+    ctx.report(error.with_note("The output is declared here.", Some(declared.span.clone())));
+}
+
+/// Whether running `stmts` always ends with a `return`. The JS of a literal
+/// is trusted to return.
+fn returns(stmts: &[ast::FnStmt]) -> bool {
+    let last = stmts
+        .iter()
+        .rev()
+        .find(|stmt| !matches!(stmt, ast::FnStmt::Noop));
+
+    match last {
+        Some(ast::FnStmt::Ret(_) | ast::FnStmt::LitJs(_) | ast::FnStmt::Error(_)) => true,
+        Some(ast::FnStmt::If(v)) => v
+            .falsy
+            .as_ref()
+            .is_some_and(|falsy| returns(&v.truthy.stmts) && returns(&falsy.stmts)),
+        Some(ast::FnStmt::Match(v)) => v.arms.iter().all(|arm| returns(&arm.body.stmts)),
+        _ => false,
+    }
+}
+
+/// Reports the lowercased names in `ty` that are not type parameters in
+/// scope, type parameters have to be declared like `fn nth<a>`.
+fn check_params(ctx: &mut Context, ast: &ast::Fn, ty: &ast::Type) {
+    let ast::Type::Ident(id) = ty else {
+        return;
+    };
+
+    let mut ids = vec![id];
+
+    while let Some(id) = ids.pop() {
+        ids.extend(&id.generics);
+
+        let name = &id.string;
+
+        if !id.generics.is_empty()
+            || !name.starts_with(char::is_lowercase)
+            || ctx.type_params.iter().any(|param| &param.string == name)
+        {
+            continue;
+        }
+
+        let message = if ast.anonymous {
+            format!("The type parameter {name} is not declared.")
+        } else {
+            format!(
+                "The type parameter {name} is not declared, write fn {}<{name}> to declare it.",
+                ast.id.string
+            )
+        };
+
+        ctx.report_custom(id, &message);
+    }
+}
+
+/// Checks `stmts` and returns the type of the value they end with.
+pub fn check_body(ctx: &mut Context, stmts: &[ast::FnStmt]) -> Type {
+    let mut ty = Type::Unknown;
+
+    for stmt in stmts
+        .iter()
+        .filter(|stmt| !matches!(stmt, ast::FnStmt::Noop))
+    {
+        ty = match stmt {
+            ast::FnStmt::Let(v) => {
+                statement_let::check(ctx, v);
+                Type::Unknown
+            }
+            ast::FnStmt::FnCall(v) => fn_call::check(ctx, v),
+            ast::FnStmt::MemberAccess(v) => member_access::check(ctx, v),
+            ast::FnStmt::If(v) => statement_if::check(ctx, v),
+            ast::FnStmt::Match(v) => statement_match::check(ctx, v),
+            ast::FnStmt::Ret(v) => {
+                statement_return::check(ctx, v);
+                Type::Unknown
+            }
+            ast::FnStmt::Expr(v) => expression::check(ctx, v),
+            ast::FnStmt::LitJs(_) | ast::FnStmt::Noop | ast::FnStmt::Error(_) => Type::Unknown,
+        };
+    }
+
+    ty
+}
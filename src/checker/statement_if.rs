@@ -0,0 +1,36 @@
+use crate::ast;
+
+use super::{context::Context, expression, statement_fn, types::Type};
+
+/// Checks an `if`, the type of its value is the one its branches share.
+pub fn check(ctx: &mut Context, ast: &ast::If) -> Type {
+    let test = expression::check(ctx, &ast.test);
+
+    expression::expect(
+        ctx,
+        &ast.test,
+        &Type::boolean(),
+        &test,
+        "The condition of an if expects",
+    );
+
+    let truthy = check_block(ctx, &ast.truthy);
+
+    match &ast.falsy {
+        Some(falsy) => {
+            let falsy = check_block(ctx, falsy);
+
+            if truthy.accepts(&falsy) {
+                truthy.or(falsy)
+            } else {
+                Type::Unknown
+            }
+        }
+        None => Type::Unknown,
+    }
+}
+
+/// Checks `block` in a scope of its own and returns the type of its value.
+pub fn check_block(ctx: &mut Context, block: &ast::Block) -> Type {
+    ctx.scoped(|ctx| statement_fn::check_body(ctx, &block.stmts))
+}
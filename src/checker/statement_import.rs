@@ -0,0 +1,12 @@
+use crate::ast;
+
+use super::{context::Context, types::Type};
+
+/// Adds the imported names to `ctx` as variables of the type they are
+/// declared with, names without one are of unknown type.
+pub fn register(ctx: &mut Context, ast: &ast::Import) {
+    for name in ast.bindings() {
+        ctx.var_types
+            .insert(name.id.string.clone(), Type::from(&name.ty));
+    }
+}
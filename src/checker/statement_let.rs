@@ -0,0 +1,39 @@
+use crate::ast;
+
+use super::{context::Context, expression};
+
+/// Checks the initializer against the declared type, the variable then has
+/// the declared type or else the type of its initializer.
+pub fn check(ctx: &mut Context, ast: &ast::Let) {
+    let expected = ctx.resolve_type(&ast.ty);
+    let found = expression::check_expected(ctx, &ast.expr, &expected);
+
+    let ty = match &ast.ty {
+        ast::Type::Ident(declared) => {
+            if !expected.accepts(&found) {
+                let error = ctx
+                    .error(
+                        &ast.expr,
+                        &format!(
+                            "The variable {} is declared as {expected}, found {found}.",
+                            ast.id
+                        ),
+                    )
+                    .with_note("The type is declared here.", Some(declared.span.clone()));
+
+                ctx.report(error);
+            }
+
+            expected.or(found)
+        }
+        ast::Type::Default => found,
+    };
+
+    // The lets of a mod are known by the name they are generated with, `M_x`.
+    let name = match ctx.current_fn {
+        None => ctx.fn_path(&ast.id),
+        Some(_) => ast.id.clone(),
+    };
+
+    ctx.var_types.insert(name, ty);
+}
@@ -0,0 +1,377 @@
+use crate::{ast, span::Span};
+
+use super::{context::Context, expression, statement_fn, types::Type, variant};
+
+/// Checks the arms of a `match` with the names their patterns bind, and that
+/// they handle every possible value. The type of its value is the one its arms
+/// share.
+pub fn check(ctx: &mut Context, ast: &ast::Match) -> Type {
+    let subject = expression::check(ctx, &ast.expr);
+    check_arms(ctx, ast);
+    let mut result: Option<Type> = None;
+
+    for arm in &ast.arms {
+        let ty = ctx.scoped(|ctx| {
+            bind(ctx, &arm.pattern, &subject);
+            statement_fn::check_body(ctx, &arm.body.stmts)
+        });
+
+        result = match result {
+            None => Some(ty),
+            Some(result) if result.accepts(&ty) => Some(result.or(ty)),
+            Some(_) => Some(Type::Unknown),
+        };
+    }
+
+    result.unwrap_or(Type::Unknown)
+}
+
+/// Declares the names `pattern` binds when it matches a value of type `ty`.
+fn bind(ctx: &mut Context, pattern: &ast::Pattern, ty: &Type) {
+    match pattern {
+        ast::Pattern::Variant(v) => {
+            let fields = match ty {
+                Type::Named(name, args) => ctx.enum_defs.get(name).and_then(|enum_def| {
+                    enum_def
+                        .variants
+                        .iter()
+                        .find(|variant| variant.id.string == v.id.string)
+                        .map(|variant| variant::field_types(enum_def, variant, args))
+                }),
+                Type::Param(_) | Type::Fn(..) | Type::Unknown => None,
+            }
+            .unwrap_or_default();
+
+            for (idx, arg) in v.args.iter().enumerate() {
+                bind(ctx, arg, fields.get(idx).unwrap_or(&Type::Unknown));
+            }
+        }
+        ast::Pattern::Literal(v) => {
+            let found = expression::check(ctx, v);
+
+            if !ty.accepts(&found) {
+                ctx.report_custom(
+                    v,
+                    &format!("The matched value is {ty}, this pattern is {found}."),
+                );
+            }
+        }
+        ast::Pattern::Wildcard(_) => {}
+        ast::Pattern::Binding(v) => {
+            ctx.var_types.insert(v.string.clone(), ty.clone());
+        }
+    }
+}
+
+/// Checks the variants used by the patterns and that the arms handle every
+/// possible value.
+fn check_arms(ctx: &mut Context, ast: &ast::Match) {
+    let variants = ast
+        .arms
+        .iter()
+        .filter_map(|arm| match &arm.pattern {
+            ast::Pattern::Variant(v) => Some(v),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // A variant no enum has is reported on its own, not against the others.
+    let known = variants
+        .iter()
+        .copied()
+        .filter(|variant| !enums_with(ctx, &[variant]).is_empty())
+        .collect::<Vec<_>>();
+    let candidates = enums_with(ctx, &known);
+
+    let mut valid = true;
+
+    for arm in &ast.arms {
+        valid &= check_pattern(ctx, &arm.pattern, &candidates);
+    }
+
+    if let Some(idx) = ast.arms.iter().position(|arm| irrefutable(&arm.pattern)) {
+        if let Some(arm) = ast.arms.get(idx + 1) {
+            ctx.report_custom(
+                &arm.pattern,
+                "This arm is never reached, the arm before it matches every value.",
+            );
+        }
+
+        return;
+    }
+
+    // The cases a pattern names wrongly cannot tell what is missing.
+    if !valid {
+        return;
+    }
+
+    if !variants.is_empty() && candidates.len() > 1 {
+        let names = candidates
+            .iter()
+            .map(|enum_def| enum_def.id.string.clone())
+            .collect::<Vec<_>>();
+
+        ctx.report_custom(
+            ast,
+            &format!(
+                "This match could be on {}, add a `_` arm to handle the other cases.",
+                names.join(" or ")
+            ),
+        );
+
+        return;
+    }
+
+    let rows = ast
+        .arms
+        .iter()
+        .map(|arm| vec![arm.pattern.clone()])
+        .collect::<Vec<_>>();
+    let missing = witnesses(ctx, &rows, 1, true)
+        .into_iter()
+        .map(|witness| match witness.join(", ").as_str() {
+            "_" => "a `_` arm".to_string(),
+            witness => witness.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    let error = ctx.error(
+        ast,
+        &format!(
+            "This match does not handle every case, it is missing {}.",
+            missing.join(", ")
+        ),
+    );
+
+    let diagnostic = match candidates.as_slice() {
+        [enum_def] if !variants.is_empty() => error.with_note_in(
+            &ctx.def_file(&enum_def.id.string),
+            &format!("The enum {} is declared here.", enum_def.id.string),
+            Some(enum_def.span.clone()),
+        ),
+        _ => error,
+    };
+
+    ctx.report(diagnostic);
+}
+
+/// The enums declaring every one of `variants`, sorted by name.
+fn enums_with(ctx: &Context, variants: &[&ast::VariantPattern]) -> Vec<ast::Enum> {
+    let mut enums = ctx
+        .enum_defs
+        .values()
+        .filter(|enum_def| {
+            variants.iter().all(|variant| {
+                enum_def
+                    .variants
+                    .iter()
+                    .any(|v| v.id.string == variant.id.string)
+            })
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    enums.sort_by(|a, b| a.id.string.cmp(&b.id.string));
+    enums
+}
+
+/// Checks that the variants of `pattern` exist in one of `candidates` and are
+/// given as many patterns as they carry values, tells whether they do.
+fn check_pattern(ctx: &mut Context, pattern: &ast::Pattern, candidates: &[ast::Enum]) -> bool {
+    let ast::Pattern::Variant(pattern) = pattern else {
+        return true;
+    };
+
+    let name = &pattern.id.string;
+    let variants = candidates
+        .iter()
+        .filter_map(|enum_def| {
+            enum_def
+                .variants
+                .iter()
+                .find(|v| &v.id.string == name)
+                .map(|variant| (enum_def, variant))
+        })
+        .collect::<Vec<_>>();
+
+    let (enum_def, variant) = match variants.first() {
+        Some(first) => *first,
+        None if enums_with(ctx, &[pattern]).is_empty() => {
+            ctx.report_custom(&pattern.id, &format!("No enum has the variant {name}."));
+            return false;
+        }
+        None => {
+            ctx.report_custom(
+                &pattern.id,
+                &format!("The variant {name} does not belong to the same enum as the other arms."),
+            );
+            return false;
+        }
+    };
+
+    if !variants
+        .iter()
+        .any(|(_, variant)| variant.fields.len() == pattern.args.len())
+    {
+        let diagnostic = ctx
+            .error(
+                pattern,
+                &format!(
+                    "The variant {}.{name} carries {} value(s) but the pattern has {}.",
+                    enum_def.id.string,
+                    variant.fields.len(),
+                    pattern.args.len()
+                ),
+            )
+            .with_note_in(
+                &ctx.def_file(&enum_def.id.string),
+                "The variant is declared here.",
+                Some(variant.span.clone()),
+            );
+        ctx.report(diagnostic);
+        return false;
+    }
+
+    let mut valid = true;
+
+    for arg in &pattern.args {
+        if let ast::Pattern::Variant(v) = arg {
+            let candidates = enums_with(ctx, &[v]);
+
+            valid &= check_pattern(ctx, arg, &candidates);
+        }
+    }
+
+    valid
+}
+
+fn irrefutable(pattern: &ast::Pattern) -> bool {
+    matches!(
+        pattern,
+        ast::Pattern::Wildcard(_) | ast::Pattern::Binding(_)
+    )
+}
+
+/// Examples of values that no row of `rows` matches, one for every
+/// constructor of the first column when `all` is set and only the first one
+/// found otherwise. Every row holds `width` patterns.
+fn witnesses(
+    ctx: &Context,
+    rows: &[Vec<ast::Pattern>],
+    width: usize,
+    all: bool,
+) -> Vec<Vec<String>> {
+    if width == 0 {
+        return if rows.is_empty() {
+            vec![vec![]]
+        } else {
+            vec![]
+        };
+    }
+
+    let heads = rows.iter().map(|row| &row[0]).collect::<Vec<_>>();
+    let mut result = vec![];
+
+    match constructors(ctx, &heads) {
+        Some(constructors) => {
+            for (name, arity) in constructors {
+                // The rows matching `name`, with its values in place of the
+                // first column.
+                let specialized = rows
+                    .iter()
+                    .filter_map(|row| {
+                        let args = match &row[0] {
+                            head if irrefutable(head) => {
+                                vec![ast::Pattern::Wildcard(Span::empty()); arity]
+                            }
+                            head if constructor_name(head).as_ref() == Some(&name) => match head {
+                                ast::Pattern::Variant(v) => v.args.clone(),
+                                _ => vec![],
+                            },
+                            _ => return None,
+                        };
+
+                        Some(args.into_iter().chain(row[1..].iter().cloned()).collect())
+                    })
+                    .collect::<Vec<_>>();
+
+                let Some(witness) = witnesses(ctx, &specialized, arity + width - 1, false)
+                    .into_iter()
+                    .next()
+                else {
+                    continue;
+                };
+
+                let (args, rest) = witness.split_at(arity);
+                let head = if args.is_empty() {
+                    name
+                } else {
+                    format!("{name}({})", args.join(", "))
+                };
+
+                result.push(std::iter::once(head).chain(rest.to_vec()).collect());
+
+                if !all {
+                    break;
+                }
+            }
+        }
+        None => {
+            let default = rows
+                .iter()
+                .filter(|row| irrefutable(&row[0]))
+                .map(|row| row[1..].to_vec())
+                .collect::<Vec<_>>();
+
+            if let Some(witness) = witnesses(ctx, &default, width - 1, false)
+                .into_iter()
+                .next()
+            {
+                result.push(std::iter::once("_".to_string()).chain(witness).collect());
+            }
+        }
+    }
+
+    result
+}
+
+/// Every value `heads` could be built with, along with how many values each
+/// carries. `None` when there are too many of them to list, like strings.
+fn constructors(ctx: &Context, heads: &[&ast::Pattern]) -> Option<Vec<(String, usize)>> {
+    let variants = heads
+        .iter()
+        .filter_map(|head| match head {
+            ast::Pattern::Variant(v) => Some(v),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if !variants.is_empty() {
+        return match enums_with(ctx, &variants).as_slice() {
+            [enum_def] => Some(
+                enum_def
+                    .variants
+                    .iter()
+                    .map(|v| (format!(".{}", v.id.string), v.fields.len()))
+                    .collect(),
+            ),
+            _ => None,
+        };
+    }
+
+    heads
+        .iter()
+        .any(|head| matches!(head, ast::Pattern::Literal(ast::Expr::LitBoolean(_))))
+        .then(|| vec![("true".to_string(), 0), ("false".to_string(), 0)])
+}
+
+fn constructor_name(pattern: &ast::Pattern) -> Option<String> {
+    match pattern {
+        ast::Pattern::Variant(v) => Some(format!(".{}", v.id.string)),
+        ast::Pattern::Literal(ast::Expr::LitBoolean(v)) => Some(v.value.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,31 @@
+use crate::ast;
+
+use super::{context::Context, statement_fn, statement_let};
+
+pub fn register(ctx: &mut Context, module: &ast::Mod) {
+    let module_path = module.path.replace('.', "_");
+
+    for stmt in &module.stmts {
+        if let ast::Stmt::Fn(v) = stmt {
+            ctx.add_fn(format!("{module_path}_{}", v.id.string), v.clone());
+        }
+    }
+
+    ctx.mod_defs.insert(module.path.clone(), module.clone());
+}
+
+pub fn check(ctx: &mut Context, module: &ast::Mod) {
+    let module_path = std::mem::replace(&mut ctx.module_path, module.path.replace('.', "_"));
+
+    for stmt in &module.stmts {
+        match stmt {
+            ast::Stmt::Fn(v) => {
+                statement_fn::check(ctx, v);
+            }
+            ast::Stmt::Let(v) => statement_let::check(ctx, v),
+            _ => {}
+        }
+    }
+
+    ctx.module_path = module_path;
+}
@@ -0,0 +1,37 @@
+use crate::ast;
+
+use super::{context::Context, expression};
+
+/// Checks the returned value against the output of the fn, fns without a
+/// known one can return anything.
+pub fn check(ctx: &mut Context, ast: &ast::Return) {
+    let expected = ctx.output.clone();
+    let found = expression::check_expected(ctx, &ast.expr, &expected);
+
+    ctx.returned = ctx.returned.clone().or(found.clone());
+
+    let Some(fn_def) = ctx.current_fn.clone() else {
+        return;
+    };
+
+    if expected.accepts(&found) {
+        return;
+    }
+
+    let message = if fn_def.anonymous {
+        format!("This function returns {expected}, found {found}.")
+    } else {
+        format!(
+            "The function {} returns {expected}, found {found}.",
+            fn_def.id.string
+        )
+    };
+    let error = match &fn_def.output {
+        ast::Type::Ident(output) => ctx
+            .error(&ast.expr, &message)
+            .with_note("The output is declared here.", Some(output.span.clone())),
+        ast::Type::Default => ctx.error(&ast.expr, &message),
+    };
+
+    ctx.report(error);
+}
@@ -0,0 +1,138 @@
+use crate::{ast, generator, loader::UsedFile, span::Span};
+
+use super::{context::Context, root, types::Type};
+
+/// Adds the fns, mods, records and enums of the used file to `ctx`. A file
+/// that cannot be read or parsed adds nothing, the generator reports it.
+pub fn register(ctx: &mut Context, ast: &ast::Use) {
+    let (file_path, _) = generator::resolve_use(&ctx.file_path, ast);
+
+    if let Some(UsedFile::Parsed(root_ast)) = ctx.used_files.clone().get(&file_path) {
+        register_file(ctx, root_ast.clone());
+    }
+}
+
+/// Adds the fns, mods, records, enums and pub lets of another file to `ctx`,
+/// along with the file they are declared in. The ones that are not pub are
+/// marked, their uses get reported. The files it uses in turn are not
+/// imported.
+pub fn register_file(ctx: &mut Context, root_ast: ast::Root) {
+    let file_path = root_ast.path.clone();
+    let def_paths = def_paths(&root_ast.stmts);
+
+    for stmt in &root_ast.stmts {
+        match stmt {
+            ast::Stmt::Let(v) if v.public => {
+                ctx.var_types.insert(v.id.clone(), Type::from(&v.ty));
+            }
+            ast::Stmt::Mod(v) => {
+                for stmt in &v.stmts {
+                    if let ast::Stmt::Let(v_let) = stmt {
+                        if v_let.public {
+                            let path = format!("{}_{}", v.path.replace('.', "_"), v_let.id);
+
+                            ctx.var_types.insert(path, Type::from(&v_let.ty));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let stmts = root_ast
+        .stmts
+        .into_iter()
+        .filter(|stmt| {
+            matches!(
+                stmt,
+                ast::Stmt::Fn(_) | ast::Stmt::Mod(_) | ast::Stmt::Record(_) | ast::Stmt::Enum(_)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    root::register(ctx, &stmts);
+
+    for (path, public) in def_paths {
+        ctx.def_files.insert(path.clone(), file_path.clone());
+
+        if !public {
+            ctx.private_defs.insert(path, file_path.clone());
+        }
+    }
+}
+
+/// `root_ast` with its definitions pointing nowhere, like the core library
+/// whose source is not in any file.
+pub fn unlocated(root_ast: ast::Root) -> ast::Root {
+    let stmts = root_ast
+        .stmts
+        .into_iter()
+        .map(|stmt| match stmt {
+            ast::Stmt::Fn(v) => ast::Stmt::Fn(unlocated_fn(v)),
+            ast::Stmt::Mod(v) => ast::Stmt::Mod(ast::Mod {
+                stmts: v
+                    .stmts
+                    .into_iter()
+                    .map(|stmt| match stmt {
+                        ast::Stmt::Fn(v) => ast::Stmt::Fn(unlocated_fn(v)),
+                        stmt => stmt,
+                    })
+                    .collect(),
+                span: Span::empty(),
+                ..v
+            }),
+            ast::Stmt::Record(v) => ast::Stmt::Record(ast::Record {
+                span: Span::empty(),
+                ..v
+            }),
+            ast::Stmt::Enum(v) => ast::Stmt::Enum(ast::Enum {
+                span: Span::empty(),
+                ..v
+            }),
+            stmt => stmt,
+        })
+        .collect();
+
+    ast::Root { stmts, ..root_ast }
+}
+
+/// The names the definitions among `stmts` are registered with, and whether
+/// other files can use them. Externs only declare what is there anyway, they
+/// are never private.
+fn def_paths(stmts: &[ast::Stmt]) -> Vec<(String, bool)> {
+    let is_public = |v: &ast::Fn| v.public || v.external;
+
+    stmts
+        .iter()
+        .flat_map(|stmt| match stmt {
+            ast::Stmt::Fn(v) => vec![(v.id.string.clone(), is_public(v))],
+            ast::Stmt::Let(v) => vec![(v.id.clone(), v.public)],
+            ast::Stmt::Enum(v) => vec![(v.id.string.clone(), v.public)],
+            ast::Stmt::Record(v) => vec![(v.id.string.clone(), true)],
+            ast::Stmt::Mod(v) => v
+                .stmts
+                .iter()
+                .filter_map(|stmt| match stmt {
+                    ast::Stmt::Fn(v_fn) => Some((
+                        format!("{}_{}", v.path.replace('.', "_"), v_fn.id.string),
+                        is_public(v_fn),
+                    )),
+                    ast::Stmt::Let(v_let) => Some((
+                        format!("{}_{}", v.path.replace('.', "_"), v_let.id),
+                        v_let.public,
+                    )),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        })
+        .collect()
+}
+
+fn unlocated_fn(ast_fn: ast::Fn) -> ast::Fn {
+    ast::Fn {
+        span: Span::empty(),
+        ..ast_fn
+    }
+}
@@ -0,0 +1,219 @@
+use std::{collections::HashMap, fmt};
+
+use crate::ast;
+
+/// The type of a value, as far as the checker can tell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    /// A value whose type cannot be told, like a JS literal or an argument
+    /// declared without a type. It fits anywhere.
+    Unknown,
+    /// A type parameter of the fn being checked, the `a` of `fn nth<a>`. Only
+    /// values of that same parameter fit it.
+    Param(String),
+    /// `Number`, `Array<String>`, a record or an enum.
+    Named(String, Vec<Type>),
+    /// `Fn<Number, String>`, the types of the arguments and the output.
+    Fn(Vec<Type>, Box<Type>),
+}
+
+impl Type {
+    pub fn named(name: &str) -> Type {
+        Type::Named(name.to_string(), vec![])
+    }
+
+    pub fn number() -> Type {
+        Type::named("Number")
+    }
+
+    pub fn string() -> Type {
+        Type::named("String")
+    }
+
+    pub fn boolean() -> Type {
+        Type::named("Boolean")
+    }
+
+    pub fn element() -> Type {
+        Type::named("Element")
+    }
+
+    /// Whether a value of type `other` can be used where `self` is expected.
+    pub fn accepts(&self, other: &Type) -> bool {
+        match (self, other) {
+            (Type::Unknown, _) | (_, Type::Unknown) => true,
+            (Type::Param(name), Type::Param(other_name)) => name == other_name,
+            (Type::Named(name, args), Type::Named(other_name, other_args)) => {
+                name == other_name
+                    && args
+                        .iter()
+                        .zip(other_args)
+                        .all(|(arg, other_arg)| arg.accepts(other_arg))
+            }
+            (Type::Fn(args, output), Type::Fn(other_args, other_output)) => {
+                args.len() == other_args.len()
+                    && args
+                        .iter()
+                        .zip(other_args)
+                        .all(|(arg, other_arg)| other_arg.accepts(arg))
+                    && output.accepts(other_output)
+            }
+            _ => false,
+        }
+    }
+
+    /// `self` with the names of `params` turned into type parameters.
+    pub fn with_params(self, params: &[ast::Ident]) -> Type {
+        match self {
+            Type::Named(name, args)
+                if args.is_empty() && params.iter().any(|p| p.string == name) =>
+            {
+                Type::Param(name)
+            }
+            Type::Named(name, args) => Type::Named(
+                name,
+                args.into_iter()
+                    .map(|arg| arg.with_params(params))
+                    .collect(),
+            ),
+            Type::Fn(args, output) => Type::Fn(
+                args.into_iter()
+                    .map(|arg| arg.with_params(params))
+                    .collect(),
+                Box::new(output.with_params(params)),
+            ),
+            ty => ty,
+        }
+    }
+
+    /// Binds the type parameters in `self` to the parts of `other` found in
+    /// their place. A parameter keeps the first type it is bound to.
+    pub fn unify(&self, other: &Type, bindings: &mut HashMap<String, Type>) {
+        match (self, other) {
+            (Type::Param(name), other) => {
+                let bound = bindings.entry(name.clone()).or_insert(Type::Unknown);
+
+                *bound = bound.clone().or(other.clone());
+            }
+            (Type::Named(name, args), Type::Named(other_name, other_args))
+                if name == other_name =>
+            {
+                for (arg, other_arg) in args.iter().zip(other_args) {
+                    arg.unify(other_arg, bindings);
+                }
+            }
+            (Type::Fn(args, output), Type::Fn(other_args, other_output)) => {
+                for (arg, other_arg) in args.iter().zip(other_args) {
+                    arg.unify(other_arg, bindings);
+                }
+
+                output.unify(other_output, bindings);
+            }
+            _ => {}
+        }
+    }
+
+    /// `self` with its type parameters replaced by the types they are bound to
+    /// in `bindings`, unbound ones fit anything.
+    pub fn substitute(&self, bindings: &HashMap<String, Type>) -> Type {
+        match self {
+            Type::Param(name) => bindings.get(name).cloned().unwrap_or(Type::Unknown),
+            Type::Named(name, args) => Type::Named(
+                name.clone(),
+                args.iter().map(|arg| arg.substitute(bindings)).collect(),
+            ),
+            Type::Fn(args, output) => Type::Fn(
+                args.iter().map(|arg| arg.substitute(bindings)).collect(),
+                Box::new(output.substitute(bindings)),
+            ),
+            Type::Unknown => Type::Unknown,
+        }
+    }
+
+    /// The type of `.await` on a value of this type, `String` for
+    /// `Promise<String>`.
+    pub fn awaited(&self) -> Type {
+        match self {
+            Type::Named(name, args) if name == "Promise" => {
+                args.first().cloned().unwrap_or(Type::Unknown)
+            }
+            _ => Type::Unknown,
+        }
+    }
+
+    /// The type an async fn with this output gives, `Promise<String>` for
+    /// `String`. A `Promise` stays as it is.
+    pub fn promised(self) -> Type {
+        if self.is("Promise") {
+            self
+        } else {
+            Type::Named("Promise".into(), vec![self])
+        }
+    }
+
+    pub fn is(&self, name: &str) -> bool {
+        matches!(self, Type::Named(v, _) if v == name)
+    }
+
+    /// `self`, unless it is unknown and `other` is not.
+    pub fn or(self, other: Type) -> Type {
+        match self {
+            Type::Unknown => other,
+            ty => ty,
+        }
+    }
+}
+
+impl From<&ast::Type> for Type {
+    fn from(ty: &ast::Type) -> Self {
+        match ty {
+            ast::Type::Default => Type::Unknown,
+            ast::Type::Ident(id) => Type::from(id),
+        }
+    }
+}
+
+impl From<&ast::Ident> for Type {
+    fn from(id: &ast::Ident) -> Self {
+        // The last type of `Fn<Number, String>` is the output, a bare `Fn`
+        // can be any fn.
+        if id.string == "Fn" {
+            return match id.generics.split_last() {
+                Some((output, args)) => Type::Fn(
+                    args.iter().map(Type::from).collect(),
+                    Box::new(Type::from(output)),
+                ),
+                None => Type::Unknown,
+            };
+        }
+
+        Type::Named(
+            id.string.clone(),
+            id.generics.iter().map(Type::from).collect(),
+        )
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Unknown => write!(f, "_"),
+            Type::Param(name) => write!(f, "{name}"),
+            Type::Named(name, args) if args.is_empty() => write!(f, "{name}"),
+            Type::Named(name, args) => {
+                let args = args.iter().map(Type::to_string).collect::<Vec<_>>();
+
+                write!(f, "{name}<{}>", args.join(", "))
+            }
+            Type::Fn(args, output) => {
+                let types = args
+                    .iter()
+                    .chain([output.as_ref()])
+                    .map(Type::to_string)
+                    .collect::<Vec<_>>();
+
+                write!(f, "Fn<{}>", types.join(", "))
+            }
+        }
+    }
+}
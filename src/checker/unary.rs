@@ -0,0 +1,17 @@
+use crate::ast;
+
+use super::{context::Context, expression, types::Type};
+
+/// Checks that `-` is given a Number and `!` a Boolean.
+pub fn check(ctx: &mut Context, ast: &ast::Unary) -> Type {
+    let found = expression::check(ctx, &ast.expr);
+    let expected = match ast.op {
+        ast::UnaryOp::Neg => Type::number(),
+        ast::UnaryOp::Not => Type::boolean(),
+    };
+    let message = format!("The operator {} expects", ast.op.value());
+
+    expression::expect(ctx, &ast.expr, &expected, &found, &message);
+
+    expected
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use crate::{ast, span::Spanned};
+
+use super::{context::Context, expression, types::Type};
+
+/// Checks `.Some(x)`, looking the variant up in every known enum. A name
+/// shared by several enums has to be written with its enum, `Option.Some(x)`.
+pub fn check(ctx: &mut Context, ast: &ast::Variant) -> Type {
+    let name = &ast.id.string;
+    let mut enums = ctx
+        .enum_defs
+        .values()
+        .filter(|enum_def| enum_def.variants.iter().any(|v| &v.id.string == name))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    enums.sort_by(|a, b| a.id.string.cmp(&b.id.string));
+
+    match enums.as_slice() {
+        [enum_def] => return check_qualified(ctx, enum_def, &ast.id, &ast.args, ast),
+        [] => ctx.report_custom(&ast.id, &format!("No enum has the variant {name}.")),
+        _ => {
+            let names = enums
+                .iter()
+                .map(|enum_def| enum_def.id.string.clone())
+                .collect::<Vec<_>>();
+
+            ctx.report_custom(
+                &ast.id,
+                &format!(
+                    "The variant {name} is ambiguous, it is defined by {}. Write {}.{name} instead.",
+                    names.join(", "),
+                    names[0]
+                ),
+            );
+        }
+    }
+
+    for arg in &ast.args {
+        expression::check(ctx, arg);
+    }
+
+    Type::Unknown
+}
+
+/// Checks the values given to the variant `id` of `enum_def`. The type
+/// parameters of the enum take the types of the values given for them.
+pub fn check_qualified(
+    ctx: &mut Context,
+    enum_def: &ast::Enum,
+    id: &ast::Ident,
+    args: &[ast::Expr],
+    spanned: &dyn Spanned,
+) -> Type {
+    let enum_name = &enum_def.id.string;
+
+    ctx.check_public(id, enum_name, &format!("The enum {enum_name}"));
+
+    let params = &enum_def.id.generics;
+    let fields = match enum_def.variants.iter().find(|v| v.id.string == id.string) {
+        Some(variant) if variant.fields.len() != args.len() => {
+            let diagnostic = ctx
+                .error(
+                    spanned,
+                    &format!(
+                        "The variant {enum_name}.{} takes {} value(s) but {} were given.",
+                        id.string,
+                        variant.fields.len(),
+                        args.len()
+                    ),
+                )
+                .with_note_in(
+                    &ctx.def_file(enum_name),
+                    "The variant is declared here.",
+                    Some(variant.span.clone()),
+                );
+            ctx.report(diagnostic);
+
+            variant.fields.clone()
+        }
+        Some(variant) => variant.fields.clone(),
+        None => {
+            let diagnostic = ctx
+                .error(
+                    id,
+                    &format!("The enum {enum_name} has no variant {}.", id.string),
+                )
+                .with_note_in(
+                    &ctx.def_file(enum_name),
+                    &format!("The enum {enum_name} is declared here."),
+                    Some(enum_def.span.clone()),
+                );
+            ctx.report(diagnostic);
+
+            vec![]
+        }
+    };
+    let mut bindings = HashMap::new();
+
+    for (idx, arg) in args.iter().enumerate() {
+        let found = expression::check(ctx, arg);
+
+        let Some(field) = fields.get(idx) else {
+            continue;
+        };
+
+        let expected = Type::from(field).with_params(params);
+
+        expected.unify(&found, &mut bindings);
+
+        let message = format!("The variant {enum_name}.{} expects", id.string);
+
+        expression::expect(ctx, arg, &expected.substitute(&bindings), &found, &message);
+    }
+
+    Type::Named(
+        enum_name.clone(),
+        params
+            .iter()
+            .map(|param| {
+                bindings
+                    .get(&param.string)
+                    .cloned()
+                    .unwrap_or(Type::Unknown)
+            })
+            .collect(),
+    )
+}
+
+/// The types of the values `variant` holds in an `enum_def` of type
+/// `enum_def<args>`.
+pub fn field_types(enum_def: &ast::Enum, variant: &ast::EnumVariant, args: &[Type]) -> Vec<Type> {
+    let params = &enum_def.id.generics;
+    let bindings = params
+        .iter()
+        .map(|param| param.string.clone())
+        .zip(args.iter().cloned())
+        .collect::<HashMap<_, _>>();
+
+    variant
+        .fields
+        .iter()
+        .map(|field| Type::from(field).with_params(params).substitute(&bindings))
+        .collect()
+}
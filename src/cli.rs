@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage:
+    zac build <input...> [--out-dir <dir>] [--root <dir>] [--message-format <format>] [--no-prelude] [--target <target>]
+    zac build <file.zac> -o <file.jsx> [--message-format <format>] [--no-prelude] [--target <target>]
+    zac fmt <input...> [--check]
+    zac lsp
+    zac help
+    zac version
+
+Options:
+    --out-dir <dir>    Directory the generated .jsx files are written to (default: build)
+    --root <dir>       Source root the output directory structure is computed from
+                       (default: the closest directory containing every input)
+    -o <file>          Write the output of a single input file to <file>
+    --message-format <format>
+                       How diagnostics are printed: human (default) or json, one
+                       object per line on stdout
+    --no-prelude       Leave out the core library, no zac_core.js is written
+    --target <target>  How the generated files are loaded: module (default), an
+                       ES module, or script, where top-level .await is an error
+    --check            Only list the files zac fmt would change, exit with 1 if any
+
+Colors are turned off with --message-format=json or when NO_COLOR is set.";
+
+#[derive(Debug)]
+pub enum Command {
+    Build(BuildOptions),
+    Fmt(FmtOptions),
+    Lsp,
+    Help,
+    Version,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    Human,
+    Json,
+}
+
+#[derive(Debug)]
+pub struct BuildOptions {
+    pub inputs: Vec<PathBuf>,
+    pub out_dir: PathBuf,
+    pub root: Option<PathBuf>,
+    pub out_file: Option<PathBuf>,
+    pub message_format: MessageFormat,
+    pub prelude: bool,
+    pub target: zac::Target,
+}
+
+#[derive(Debug)]
+pub struct FmtOptions {
+    pub inputs: Vec<PathBuf>,
+    pub check: bool,
+}
+
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+
+    match args.next().map(|arg| arg.as_str()) {
+        Some("build") => parse_build(args.as_slice()),
+        Some("fmt") => parse_fmt(args.as_slice()),
+        Some("lsp") => Ok(Command::Lsp),
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some("version") | Some("--version") | Some("-V") => Ok(Command::Version),
+        Some(other) => Err(format!("Unknown command '{other}'.")),
+    }
+}
+
+fn parse_build(args: &[String]) -> Result<Command, String> {
+    let mut inputs = vec![];
+    let mut out_dir = None;
+    let mut root = None;
+    let mut out_file = None;
+    let mut message_format = MessageFormat::Human;
+    let mut prelude = true;
+    let mut target = zac::Target::Module;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out-dir" => out_dir = Some(expect_value(arg, args.next())?),
+            "--root" => root = Some(expect_value(arg, args.next())?),
+            "-o" | "--out-file" => out_file = Some(expect_value(arg, args.next())?),
+            "--no-prelude" => prelude = false,
+            "--message-format" => {
+                let value = expect_value(arg, args.next())?;
+                message_format = parse_message_format(&value.to_string_lossy())?
+            }
+            "--target" => {
+                let value = expect_value(arg, args.next())?;
+                target = parse_target(&value.to_string_lossy())?
+            }
+            _ if arg.starts_with("--out-dir=") => {
+                out_dir = Some(PathBuf::from(&arg["--out-dir=".len()..]))
+            }
+            _ if arg.starts_with("--root=") => root = Some(PathBuf::from(&arg["--root=".len()..])),
+            _ if arg.starts_with("--message-format=") => {
+                message_format = parse_message_format(&arg["--message-format=".len()..])?
+            }
+            _ if arg.starts_with("--target=") => target = parse_target(&arg["--target=".len()..])?,
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{arg}'.")),
+            _ => inputs.push(PathBuf::from(arg)),
+        }
+    }
+
+    if inputs.is_empty() {
+        return Err("The build command needs at least one input file or directory.".into());
+    }
+
+    if out_file.is_some() && (inputs.len() > 1 || inputs[0].is_dir()) {
+        return Err("The option '-o' can only be used with a single input file.".into());
+    }
+
+    if out_file.is_some() && out_dir.is_some() {
+        return Err("The options '-o' and '--out-dir' cannot be used together.".into());
+    }
+
+    Ok(Command::Build(BuildOptions {
+        inputs,
+        out_dir: out_dir.unwrap_or_else(|| PathBuf::from("build")),
+        root,
+        out_file,
+        message_format,
+        prelude,
+        target,
+    }))
+}
+
+fn parse_fmt(args: &[String]) -> Result<Command, String> {
+    let mut inputs = vec![];
+    let mut check = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{arg}'.")),
+            _ => inputs.push(PathBuf::from(arg)),
+        }
+    }
+
+    if inputs.is_empty() {
+        return Err("The fmt command needs at least one input file or directory.".into());
+    }
+
+    Ok(Command::Fmt(FmtOptions { inputs, check }))
+}
+
+fn parse_message_format(value: &str) -> Result<MessageFormat, String> {
+    match value {
+        "human" => Ok(MessageFormat::Human),
+        "json" => Ok(MessageFormat::Json),
+        _ => Err(format!(
+            "Unknown message format '{value}', expected 'human' or 'json'."
+        )),
+    }
+}
+
+fn parse_target(value: &str) -> Result<zac::Target, String> {
+    match value {
+        "module" => Ok(zac::Target::Module),
+        "script" => Ok(zac::Target::Script),
+        _ => Err(format!(
+            "Unknown target '{value}', expected 'module' or 'script'."
+        )),
+    }
+}
+
+fn expect_value(option: &str, value: Option<&String>) -> Result<PathBuf, String> {
+    match value {
+        Some(value) if !value.starts_with('-') => Ok(PathBuf::from(value)),
+        _ => Err(format!("The option '{option}' expects a value.")),
+    }
+}
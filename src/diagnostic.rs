@@ -0,0 +1,246 @@
+use serde_json::{json, Value};
+
+use std::borrow::Cow;
+
+use crate::{loader::Loader, span::Span, utils::line_col};
+
+/// Stable codes attached to diagnostics so tools can tell them apart without
+/// matching on the message.
+pub mod code {
+    pub const UNSUPPORTED_CHARACTER: &str = "E0001";
+    pub const UNEXPECTED_TOKEN: &str = "E0002";
+    pub const RESERVED_KEYWORD: &str = "E0003";
+    pub const SYNTAX_ERROR: &str = "E0004";
+    pub const ALREADY_DEFINED: &str = "E0100";
+    pub const COMPILE_ERROR: &str = "E0101";
+    pub const TYPE_MISMATCH: &str = "E0102";
+    pub const PRIVATE: &str = "E0103";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Error => "Error",
+            Severity::Warning => "Warning",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+
+    fn color(&self) -> &'static str {
+        match self {
+            Severity::Error => "\x1b[91m",
+            Severity::Warning => "\x1b[93m",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Note {
+    pub message: String,
+    /// The file `span` is in, a used file for the definitions it declares.
+    pub file: String,
+    pub span: Option<Span>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<&'static str>,
+    pub file: String,
+    pub message: String,
+    pub span: Span,
+    pub notes: Vec<Note>,
+}
+
+impl Diagnostic {
+    pub fn error(file: String, message: String, span: Span) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            code: None,
+            file,
+            message,
+            span,
+            notes: vec![],
+        }
+    }
+
+    pub fn warning(file: String, message: String, span: Span) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(file, message, span)
+        }
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Diagnostic {
+        self.code = Some(code);
+        self
+    }
+
+    /// Adds a note pointing at `span` of the file of the diagnostic.
+    pub fn with_note(self, message: &str, span: Option<Span>) -> Diagnostic {
+        let file = self.file.clone();
+
+        self.with_note_in(&file, message, span)
+    }
+
+    /// Adds a note pointing at `span` of `file`. Definitions of the core
+    /// library have an empty span, notes about them point nowhere.
+    pub fn with_note_in(mut self, file: &str, message: &str, span: Option<Span>) -> Diagnostic {
+        self.notes.push(Note {
+            message: message.to_string(),
+            file: file.to_string(),
+            span: span.filter(|span| span != &Span::empty()),
+        });
+        self
+    }
+
+    /// The text of the file `note` points at, `content` for the file of the
+    /// diagnostic and read through `loader` for the others.
+    fn note_content<'a>(
+        &self,
+        note: &Note,
+        content: &'a str,
+        loader: &dyn Loader,
+    ) -> Option<Cow<'a, str>> {
+        if note.file == self.file {
+            Some(Cow::Borrowed(content))
+        } else {
+            loader.load(&note.file).ok().map(Cow::Owned)
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Renders the diagnostic the way the command line shows it, with a few
+    /// lines of `content` around the primary span. The notes about other files
+    /// read them through `loader`.
+    pub fn render(&self, content: &str, loader: &dyn Loader, color: bool) -> String {
+        let paint = |code: &str| {
+            if color {
+                code.to_string()
+            } else {
+                "".to_string()
+            }
+        };
+        let (line, column) = line_col(content, self.span.from);
+        let lines = content.split('\n').collect::<Vec<_>>();
+        let first_line = line.saturating_sub(2);
+        let last_line = (line + 2).min(lines.len().saturating_sub(1));
+        let code = match self.code {
+            Some(code) => format!("[{code}]"),
+            None => "".to_string(),
+        };
+        let mut result = vec![
+            "".to_string(),
+            format!(
+                "{}{}{}{}: {}",
+                paint(self.severity.color()),
+                self.severity.label(),
+                code,
+                paint("\x1b[0m"),
+                self.message
+            ),
+            format!("{}:{}:{}", self.file, line + 1, column + 1),
+            "".to_string(),
+        ];
+
+        for (idx, source_line) in lines
+            .iter()
+            .enumerate()
+            .take(last_line + 1)
+            .skip(first_line)
+        {
+            let source_line = source_line.trim_end_matches('\r');
+
+            if idx == line {
+                result.push(format!("{:>5} | {}", idx + 1, source_line));
+                result.push(format!(
+                    "{}      | {}^{}",
+                    paint("\x1b[93m"),
+                    "-".repeat(column),
+                    paint("\x1b[0m")
+                ));
+            } else {
+                result.push(format!("      | {}", source_line));
+            }
+        }
+
+        result.push("      | ".to_string());
+
+        for note in &self.notes {
+            let note_content = self.note_content(note, content, loader);
+
+            match (&note.span, note_content) {
+                (Some(span), Some(note_content)) => {
+                    let (line, column) = line_col(&note_content, span.from);
+                    result.push(format!(
+                        "      = note: {} ({}:{}:{})",
+                        note.message,
+                        note.file,
+                        line + 1,
+                        column + 1
+                    ));
+                }
+                (Some(_), None) => {
+                    result.push(format!("      = note: {} ({})", note.message, note.file))
+                }
+                (None, _) => result.push(format!("      = note: {}", note.message)),
+            }
+        }
+
+        result.push("".to_string());
+        result.join("\n")
+    }
+
+    /// Converts the diagnostic into the object printed by `--message-format=json`.
+    /// Lines and columns are one-based, columns count characters and `range`
+    /// holds byte offsets into `content`, or into the file of a note read
+    /// through `loader`.
+    pub fn to_json(&self, content: &str, loader: &dyn Loader) -> Value {
+        let position = |content: &str, pos: usize| {
+            let (line, column) = line_col(content, pos);
+            json!({ "line": line + 1, "column": column + 1 })
+        };
+        let notes = self
+            .notes
+            .iter()
+            .map(|note| {
+                let note_content = self.note_content(note, content, loader);
+                let at = |pos: usize| note_content.as_ref().map(|content| position(content, pos));
+
+                json!({
+                    "message": note.message,
+                    "file": note.file,
+                    "range": note.span.as_ref().map(|span| json!({ "start": span.from, "end": span.to })),
+                    "start": note.span.as_ref().and_then(|span| at(span.from)),
+                    "end": note.span.as_ref().and_then(|span| at(span.to)),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "file": self.file,
+            "range": { "start": self.span.from, "end": self.span.to },
+            "start": position(content, self.span.from),
+            "end": position(content, self.span.to),
+            "severity": self.severity.name(),
+            "code": self.code,
+            "message": self.message,
+            "notes": notes,
+        })
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    span::{Span, Spanned},
+    token::Comment,
+};
+
+pub const INDENT: &str = "    ";
+pub const MAX_WIDTH: usize = 100;
+
+/// Where the context stood in the comments, to print something a second way.
+#[derive(Clone, Copy)]
+pub struct Checkpoint {
+    next_comment: usize,
+    last_end: Option<usize>,
+}
+
+pub struct Context<'a> {
+    pub source: &'a str,
+    comments: &'a [Comment],
+    next_comment: usize,
+    last_end: Option<usize>,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(source: &'a str, comments: &'a [Comment]) -> Self {
+        Context {
+            source,
+            comments,
+            next_comment: 0,
+            last_end: None,
+        }
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            next_comment: self.next_comment,
+            last_end: self.last_end,
+        }
+    }
+
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.next_comment = checkpoint.next_comment;
+        self.last_end = checkpoint.last_end;
+    }
+
+    /// Prints `items` one per line at `indent`, keeping the comments found
+    /// between them and at most one blank line wherever the source had some.
+    /// `end` is where the enclosing block closes.
+    pub fn block<T: Spanned>(
+        &mut self,
+        items: &[&T],
+        end: usize,
+        indent: usize,
+        mut format: impl FnMut(&mut Self, &T, usize) -> String,
+    ) -> Vec<String> {
+        let mut lines = vec![];
+
+        self.open_block();
+
+        for item in items {
+            let span = item.span();
+
+            lines.append(&mut self.take_comments_before(span.from, indent));
+
+            let blank_line = self.has_blank_line_before(span.from);
+            let text = format(self, item, indent);
+            let code_end = self.code_end(&span);
+
+            if blank_line {
+                lines.push("".to_string());
+            }
+
+            // Comments stuck inside an expression cannot stay there, they move
+            // right above the statement instead.
+            while let Some(comment) = self.take_comment_before(code_end) {
+                lines.push(format!("{}{}", pad(indent), comment.value));
+            }
+
+            let mut line = format!("{}{}", pad(indent), text);
+
+            match self.take_trailing_comment(code_end) {
+                Some(comment) => line = format!("{line} {comment}"),
+                None => self.last_end = Some(code_end),
+            }
+
+            lines.push(line);
+        }
+
+        lines.append(&mut self.take_comments_before(end, indent));
+        lines
+    }
+
+    /// Starts a new list of lines, blank lines are only kept between its items.
+    pub fn open_block(&mut self) {
+        self.last_end = None;
+    }
+
+    /// Takes the comments starting before `pos` as lines at `indent`.
+    pub fn take_comments_before(&mut self, pos: usize, indent: usize) -> Vec<String> {
+        let mut lines = vec![];
+
+        while let Some(comment) = self.take_comment_before(pos) {
+            if self.has_blank_line_before(comment.span.from) {
+                lines.push("".to_string());
+            }
+
+            lines.push(format!("{}{}", pad(indent), comment.value));
+            self.last_end = Some(comment.span.to);
+        }
+
+        lines
+    }
+
+    /// Takes the next comment if it sits on the same line right after `pos`.
+    pub fn take_trailing_comment(&mut self, pos: usize) -> Option<String> {
+        let comment = self.comments.get(self.next_comment)?;
+        let between = self.source.get(pos..comment.span.from)?;
+
+        if between.contains('\n') || comment.value.contains('\n') {
+            return None;
+        }
+
+        self.next_comment += 1;
+        self.last_end = Some(comment.span.to);
+
+        Some(comment.value.clone())
+    }
+
+    pub fn has_comments_before(&self, pos: usize) -> bool {
+        self.comments
+            .get(self.next_comment)
+            .is_some_and(|comment| comment.span.from < pos)
+    }
+
+    /// Where the code of `span` really ends, leaving out the whitespace and
+    /// comments the parser sometimes includes at the end of a node.
+    pub fn code_end(&self, span: &Span) -> usize {
+        let bytes = self.source.as_bytes();
+        let mut end = span.to.min(bytes.len());
+
+        loop {
+            while end > span.from && bytes[end - 1].is_ascii_whitespace() {
+                end -= 1;
+            }
+
+            match self
+                .comments
+                .iter()
+                .find(|comment| comment.span.to == end && comment.span.from >= span.from)
+            {
+                Some(comment) => end = comment.span.from,
+                None => return end,
+            }
+        }
+    }
+
+    fn take_comment_before(&mut self, pos: usize) -> Option<Comment> {
+        if !self.has_comments_before(pos) {
+            return None;
+        }
+
+        self.next_comment += 1;
+
+        Some(self.comments[self.next_comment - 1].clone())
+    }
+
+    fn has_blank_line_before(&self, pos: usize) -> bool {
+        self.last_end
+            .and_then(|end| self.source.get(end..pos))
+            .is_some_and(|between| between.matches('\n').count() >= 2)
+    }
+}
+
+pub fn pad(indent: usize) -> String {
+    INDENT.repeat(indent)
+}
+
+/// Whether `text` can be printed on a single line at `indent`.
+pub fn fits(indent: usize, text: &str) -> bool {
+    !text.contains('\n') && INDENT.len() * indent + text.chars().count() <= MAX_WIDTH
+}
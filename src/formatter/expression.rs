@@ -0,0 +1,477 @@
+use crate::{ast, span::Spanned};
+
+use super::{
+    context::{fits, pad, Context},
+    jsx_element, statement,
+};
+
+pub fn format(ctx: &mut Context, ast: &ast::Expr, indent: usize) -> String {
+    match ast {
+        ast::Expr::Id(v) => format_ident(v),
+        ast::Expr::Fn(v) => statement::format_fn(ctx, v, indent),
+        ast::Expr::FnCall(v) => format_fn_call(ctx, v, indent),
+        ast::Expr::Binary(v) if v.op == ast::BinaryOp::Pipe => format_pipe(ctx, v, indent),
+        ast::Expr::Binary(v) => {
+            let precedence = v.op.precedence();
+
+            format!(
+                "{} {} {}",
+                format_operand(ctx, &v.left, precedence, indent),
+                v.op.value(),
+                format_operand(ctx, &v.right, precedence + 1, indent)
+            )
+        }
+        ast::Expr::Unary(v) => match &v.expr {
+            ast::Expr::Unary(_) => format!("{}({})", v.op.value(), format(ctx, &v.expr, indent)),
+            expr => format!(
+                "{}{}",
+                v.op.value(),
+                format_operand(ctx, expr, ast::UNARY_PRECEDENCE, indent)
+            ),
+        },
+        ast::Expr::LitBoolean(v) => v.raw.clone(),
+        ast::Expr::LitNumber(v) => v.raw.clone(),
+        ast::Expr::LitString(v) => v.raw.clone(),
+        ast::Expr::LitJs(v) => format_js(v),
+        ast::Expr::JsxElement(v) => jsx_element::format(ctx, v, indent),
+        ast::Expr::InitArray(v) => {
+            let close = ctx.code_end(&v.span).saturating_sub(1);
+            format_list(ctx, "[", "]", &v.items, close, indent)
+        }
+        ast::Expr::InitRecord(v) => format_record(ctx, v, indent),
+        ast::Expr::MemberAccess(v) => format_member_access(ctx, v, indent),
+        ast::Expr::If(v) => format_if(ctx, v, indent, true),
+        ast::Expr::Match(v) => format_match(ctx, v, indent),
+        ast::Expr::Variant(v) => format_variant(ctx, v, indent),
+    }
+}
+
+/// Formats `expr`, in parentheses when its operator binds looser than
+/// `min_precedence`.
+fn format_operand(
+    ctx: &mut Context,
+    expr: &ast::Expr,
+    min_precedence: u8,
+    indent: usize,
+) -> String {
+    let text = format(ctx, expr, indent);
+
+    match expr {
+        ast::Expr::Binary(v) if v.op.precedence() < min_precedence => format!("({text})"),
+        _ => text,
+    }
+}
+
+pub fn format_ident(ident: &ast::Ident) -> String {
+    if ident.generics.is_empty() {
+        ident.string.clone()
+    } else {
+        let generics = ident
+            .generics
+            .iter()
+            .map(format_ident)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("{}<{generics}>", ident.string)
+    }
+}
+
+pub fn format_type(ast: &ast::Type) -> String {
+    match ast {
+        ast::Type::Default => "".to_string(),
+        ast::Type::Ident(v) => format_ident(v),
+    }
+}
+
+pub fn format_js(ast: &ast::LitJs) -> String {
+    format!("`{}`", ast.value)
+}
+
+pub fn format_fn_call(ctx: &mut Context, ast: &ast::FnCall, indent: usize) -> String {
+    let close = ctx.code_end(&ast.span).saturating_sub(1);
+    let args = format_list(ctx, "(", ")", &ast.args, close, indent);
+
+    format!("{}{args}", format_ident(&ast.id))
+}
+
+fn format_variant(ctx: &mut Context, ast: &ast::Variant, indent: usize) -> String {
+    if ast.args.is_empty() {
+        return format!(".{}", ast.id.string);
+    }
+
+    let close = ctx.code_end(&ast.span).saturating_sub(1);
+    let args = format_list(ctx, "(", ")", &ast.args, close, indent);
+
+    format!(".{}{args}", ast.id.string)
+}
+
+/// Chains are kept on one line when they fit. Otherwise every part after the
+/// first call goes on its own line, one level deeper.
+pub fn format_member_access(ctx: &mut Context, ast: &ast::MemberAccess, indent: usize) -> String {
+    let mut parts = vec![];
+
+    flatten(&ast.obj, &mut parts);
+    flatten(&ast.prop, &mut parts);
+
+    let head_len = parts
+        .iter()
+        .position(|part| matches!(part, ast::Expr::FnCall(_)))
+        .map_or(parts.len(), |idx| idx + 1);
+    let head = parts[..head_len]
+        .iter()
+        .map(|part| format(ctx, part, indent))
+        .collect::<Vec<_>>()
+        .join(".");
+    let tail = parts[head_len..]
+        .iter()
+        .map(|part| format(ctx, part, indent + 1))
+        .collect::<Vec<_>>();
+
+    let flat = std::iter::once(head.clone())
+        .chain(tail.iter().cloned())
+        .collect::<Vec<_>>()
+        .join(".");
+
+    if tail.is_empty() || fits(indent, &flat) {
+        flat
+    } else {
+        tail.iter().fold(head, |result, part| {
+            format!("{result}\n{}.{part}", pad(indent + 1))
+        })
+    }
+}
+
+/// A pipe chain stays on one line when it fits, otherwise every `|>` starts a
+/// line of its own.
+fn format_pipe(ctx: &mut Context, ast: &ast::Binary, indent: usize) -> String {
+    let mut steps = vec![&ast.right];
+    let mut head = &ast.left;
+
+    while let ast::Expr::Binary(v) = head {
+        if v.op != ast::BinaryOp::Pipe {
+            break;
+        }
+
+        steps.push(&v.right);
+        head = &v.left;
+    }
+
+    let head = format_operand(ctx, head, ast.op.precedence() + 1, indent);
+    let steps = steps
+        .into_iter()
+        .rev()
+        .map(|step| format_operand(ctx, step, ast.op.precedence() + 1, indent + 1))
+        .collect::<Vec<_>>();
+
+    let flat = std::iter::once(head.clone())
+        .chain(steps.iter().cloned())
+        .collect::<Vec<_>>()
+        .join(" |> ");
+
+    if fits(indent, &flat) {
+        flat
+    } else {
+        steps.iter().fold(head, |result, step| {
+            format!("{result}\n{}|> {step}", pad(indent + 1))
+        })
+    }
+}
+
+fn flatten<'a>(ast: &'a ast::Expr, parts: &mut Vec<&'a ast::Expr>) {
+    match ast {
+        ast::Expr::MemberAccess(v) => {
+            flatten(&v.obj, parts);
+            flatten(&v.prop, parts);
+        }
+        _ => parts.push(ast),
+    }
+}
+
+/// An `if` used as a value stays on one line when every branch is a single
+/// expression and it fits. Otherwise every branch gets its own lines.
+pub fn format_if(ctx: &mut Context, ast: &ast::If, indent: usize, as_value: bool) -> String {
+    if as_value {
+        let checkpoint = ctx.checkpoint();
+
+        match format_if_inline(ctx, ast, indent) {
+            Some(inline) if fits(indent, &inline) => return inline,
+            _ => ctx.restore(checkpoint),
+        }
+    }
+
+    let test = format(ctx, &ast.test, indent);
+    let truthy = format_block(ctx, &ast.truthy, indent);
+    let code = format!("if {test} {truthy}");
+
+    match &ast.falsy {
+        None => code,
+        Some(falsy) => match else_if(ctx, falsy) {
+            Some(else_if) => format!("{code} else {}", format_if(ctx, else_if, indent, false)),
+            None => format!("{code} else {}", format_block(ctx, falsy, indent)),
+        },
+    }
+}
+
+fn format_if_inline(ctx: &mut Context, ast: &ast::If, indent: usize) -> Option<String> {
+    if ctx.has_comments_before(ctx.code_end(&ast.span)) {
+        return None;
+    }
+
+    let test = format(ctx, &ast.test, indent);
+    let truthy = format_block_inline(ctx, &ast.truthy, indent)?;
+    let falsy = ast.falsy.as_ref()?;
+    let falsy = match else_if(ctx, falsy) {
+        Some(else_if) => format_if_inline(ctx, else_if, indent)?,
+        None => format_block_inline(ctx, falsy, indent)?,
+    };
+
+    Some(format!("if {test} {truthy} else {falsy}"))
+}
+
+fn format_block(ctx: &mut Context, block: &ast::Block, indent: usize) -> String {
+    let end = ctx.code_end(&block.span).saturating_sub(1);
+    let lines = statement::format_body(ctx, &block.stmts, end, indent + 1);
+
+    statement::braced(&lines, indent)
+}
+
+fn format_block_inline(ctx: &mut Context, block: &ast::Block, indent: usize) -> Option<String> {
+    let stmts = block
+        .stmts
+        .iter()
+        .filter(|stmt| !matches!(stmt, ast::FnStmt::Noop))
+        .collect::<Vec<_>>();
+
+    match stmts.as_slice() {
+        [ast::FnStmt::Let(_) | ast::FnStmt::Ret(_) | ast::FnStmt::If(_) | ast::FnStmt::Match(_)] => {
+            None
+        }
+        [stmt] => {
+            let text = statement::format_fn_stmt(ctx, stmt, indent);
+            (!text.contains('\n')).then(|| format!("{{ {text} }}"))
+        }
+        _ => None,
+    }
+}
+
+/// The `if` following an `else` written as `else if`.
+fn else_if<'a>(ctx: &Context, block: &'a ast::Block) -> Option<&'a ast::If> {
+    match block.stmts.as_slice() {
+        [ast::FnStmt::If(v)]
+            if ctx
+                .source
+                .get(block.span.from..)
+                .is_some_and(|source| !source.starts_with('{')) =>
+        {
+            Some(v)
+        }
+        _ => None,
+    }
+}
+
+/// Every arm of a `match` goes on its own line.
+pub fn format_match(ctx: &mut Context, ast: &ast::Match, indent: usize) -> String {
+    let expr = format(ctx, &ast.expr, indent);
+    let end = ctx.code_end(&ast.span).saturating_sub(1);
+    let arms = ast.arms.iter().collect::<Vec<_>>();
+    let lines = ctx.block(&arms, end, indent + 1, format_arm);
+
+    format!("match {expr} {}", statement::braced(&lines, indent))
+}
+
+fn format_arm(ctx: &mut Context, arm: &ast::MatchArm, indent: usize) -> String {
+    let pattern = format_pattern(ctx, &arm.pattern, indent);
+    let braced = ctx
+        .source
+        .get(arm.body.span.from..)
+        .is_some_and(|source| source.starts_with('{'));
+
+    match arm.body.stmts.as_slice() {
+        [stmt] if !braced => format!(
+            "{pattern} => {}",
+            statement::format_fn_stmt(ctx, stmt, indent)
+        ),
+        _ => format!("{pattern} => {}", format_block(ctx, &arm.body, indent)),
+    }
+}
+
+fn format_pattern(ctx: &mut Context, pattern: &ast::Pattern, indent: usize) -> String {
+    match pattern {
+        ast::Pattern::Variant(v) if v.args.is_empty() => format!(".{}", v.id.string),
+        ast::Pattern::Variant(v) => {
+            let args = v
+                .args
+                .iter()
+                .map(|arg| format_pattern(ctx, arg, indent))
+                .collect::<Vec<_>>();
+
+            format!(".{}({})", v.id.string, args.join(", "))
+        }
+        ast::Pattern::Literal(v) => format(ctx, v, indent),
+        ast::Pattern::Wildcard(_) => "_".to_string(),
+        ast::Pattern::Binding(v) => v.string.clone(),
+    }
+}
+
+fn format_record(ctx: &mut Context, ast: &ast::InitRecord, indent: usize) -> String {
+    let name = format_ident(&ast.id);
+    let close_pos = ctx.code_end(&ast.span).saturating_sub(1);
+
+    if !ctx.has_comments_before(close_pos) {
+        if ast.props.is_empty() {
+            return format!("{name} {{}}");
+        }
+
+        let checkpoint = ctx.checkpoint();
+        let props = ast
+            .props
+            .iter()
+            .map(|prop| format_record_prop(ctx, prop, indent + 1))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let inline = format!("{name} {{ {props} }}");
+
+        if fits(indent, &inline) {
+            return inline;
+        }
+
+        ctx.restore(checkpoint);
+    }
+
+    format_lines(
+        ctx,
+        &format!("{name} {{"),
+        "}",
+        &ast.props,
+        close_pos,
+        indent,
+        format_record_prop,
+    )
+}
+
+fn format_record_prop(ctx: &mut Context, prop: &ast::InitObjectProp, indent: usize) -> String {
+    match prop {
+        ast::InitObjectProp::Key(id) => id.string.clone(),
+        ast::InitObjectProp::KeyValue(id, expr) => {
+            format!("{}: {}", id.string, format(ctx, expr, indent))
+        }
+        ast::InitObjectProp::Spread(id) => format!("...{}", id.string),
+    }
+}
+
+/// Prints comma separated `items` on one line when they fit, otherwise one per
+/// line. A trailing function, element or array is allowed to span several
+/// lines right after the other items. `close_pos` is where the closing bracket
+/// sits in the source.
+fn format_list(
+    ctx: &mut Context,
+    open: &str,
+    close: &str,
+    items: &[ast::Expr],
+    close_pos: usize,
+    indent: usize,
+) -> String {
+    if items.is_empty() && !ctx.has_comments_before(close_pos) {
+        return format!("{open}{close}");
+    }
+
+    if !ctx.has_comments_before(close_pos) {
+        let checkpoint = ctx.checkpoint();
+        let texts = items
+            .iter()
+            .map(|item| format(ctx, item, indent + 1))
+            .collect::<Vec<_>>();
+        let flat = format!("{open}{}{close}", texts.join(", "));
+
+        if fits(indent, &flat) {
+            return flat;
+        }
+
+        ctx.restore(checkpoint);
+
+        if let Some(hugged) = format_hugged(ctx, open, close, items, indent) {
+            return hugged;
+        }
+
+        ctx.restore(checkpoint);
+    }
+
+    format_lines(ctx, open, close, items, close_pos, indent, format)
+}
+
+/// Prints `items` one per line between `open` and `close`, separated by commas
+/// and keeping the comments around them.
+pub fn format_lines<T: Spanned>(
+    ctx: &mut Context,
+    open: &str,
+    close: &str,
+    items: &[T],
+    close_pos: usize,
+    indent: usize,
+    mut format_item: impl FnMut(&mut Context, &T, usize) -> String,
+) -> String {
+    let mut lines = vec![];
+
+    ctx.open_block();
+
+    for (idx, item) in items.iter().enumerate() {
+        let span = item.span();
+
+        lines.append(&mut ctx.take_comments_before(span.from, indent + 1));
+
+        let separator = if idx + 1 < items.len() { "," } else { "" };
+        let mut line = format!(
+            "{}{}{separator}",
+            pad(indent + 1),
+            format_item(ctx, item, indent + 1)
+        );
+
+        if let Some(comment) = ctx.take_trailing_comment(ctx.code_end(&span)) {
+            line = format!("{line} {comment}");
+        }
+
+        lines.push(line);
+    }
+
+    lines.append(&mut ctx.take_comments_before(close_pos, indent + 1));
+
+    format!("{open}\n{}\n{}{close}", lines.join("\n"), pad(indent))
+}
+
+fn format_hugged(
+    ctx: &mut Context,
+    open: &str,
+    close: &str,
+    items: &[ast::Expr],
+    indent: usize,
+) -> Option<String> {
+    let (last, others) = items.split_last()?;
+
+    if !matches!(
+        last,
+        ast::Expr::Fn(_) | ast::Expr::JsxElement(_) | ast::Expr::InitArray(_)
+    ) {
+        return None;
+    }
+
+    let mut texts = others
+        .iter()
+        .map(|item| format(ctx, item, indent))
+        .collect::<Vec<_>>();
+
+    texts.push(format(ctx, last, indent));
+
+    let hugged = format!("{open}{}{close}", texts.join(", "));
+    let first_line = hugged.lines().next().unwrap_or_default();
+
+    if texts[..others.len()]
+        .iter()
+        .all(|text| !text.contains('\n'))
+        && fits(indent, first_line)
+    {
+        Some(hugged)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{ast, span::Spanned};
+
+use super::{
+    context::{fits, pad, Context},
+    expression,
+};
+
+/// Elements without nested elements stay on one line when they fit, anything
+/// else gets one child per line.
+pub fn format(ctx: &mut Context, ast: &ast::JsxElement, indent: usize) -> String {
+    let name = &ast.name;
+    let attrs = ast
+        .attrs
+        .iter()
+        .map(|attr| format_attr(ctx, attr, indent))
+        .map(|attr| format!(" {attr}"))
+        .collect::<String>();
+
+    if ast.self_closing {
+        return format!("<{name}{attrs} />");
+    }
+
+    let end = ctx
+        .code_end(&ast.span)
+        .saturating_sub(format!("</{name}>").len());
+
+    if ast.children.is_empty() && !ctx.has_comments_before(end) {
+        return format!("<{name}{attrs}></{name}>");
+    }
+
+    let nested = ast
+        .children
+        .iter()
+        .any(|child| matches!(child, ast::Expr::JsxElement(_)));
+
+    if !nested && !ctx.has_comments_before(end) {
+        let checkpoint = ctx.checkpoint();
+        let inline = format!(
+            "<{name}{attrs}>{}</{name}>",
+            format_inline_children(ctx, &ast.children, indent)
+        );
+
+        if fits(indent, &inline) {
+            return inline;
+        }
+
+        ctx.restore(checkpoint);
+    }
+
+    let children = ast.children.iter().collect::<Vec<_>>();
+    let lines = ctx.block(
+        &children,
+        end,
+        indent + 1,
+        |ctx, child, indent| match text(ctx, child) {
+            Some(text) => text.trim().to_string(),
+            None => format_child(ctx, child, indent),
+        },
+    );
+
+    format!(
+        "<{name}{attrs}>\n{}\n{}</{name}>",
+        lines.join("\n"),
+        pad(indent)
+    )
+}
+
+fn format_attr(ctx: &mut Context, attr: &ast::JsxElementAttribute, indent: usize) -> String {
+    match &attr.expr {
+        ast::Expr::LitString(v) => format!("{}={}", attr.name, v.raw),
+        expr => format!(
+            "{}={{{}}}",
+            attr.name,
+            expression::format(ctx, expr, indent)
+        ),
+    }
+}
+
+fn format_inline_children(ctx: &mut Context, children: &[ast::Expr], indent: usize) -> String {
+    let mut result = String::new();
+    let mut prev_is_text = false;
+
+    for child in children {
+        match text(ctx, child) {
+            Some(text) => {
+                // Every child ends up on its own line in the generated code, so
+                // two texts next to each other are read as separate words.
+                if prev_is_text {
+                    result.push(' ');
+                }
+
+                result.push_str(text);
+                prev_is_text = true;
+            }
+            None => {
+                result.push_str(&format_child(ctx, child, indent));
+                prev_is_text = false;
+            }
+        }
+    }
+
+    result.trim().to_string()
+}
+
+fn format_child(ctx: &mut Context, child: &ast::Expr, indent: usize) -> String {
+    match child {
+        ast::Expr::JsxElement(v) => format(ctx, v, indent),
+        _ => format!("{{{}}}", expression::format(ctx, child, indent)),
+    }
+}
+
+/// The text of `child` when it is plain text written between the tags rather
+/// than a string literal in braces.
+fn text<'a>(ctx: &Context, child: &'a ast::Expr) -> Option<&'a str> {
+    match child {
+        ast::Expr::LitString(v) => {
+            let span = child.span();
+            let source = ctx.source.get(span.from..span.to)?;
+
+            (source == v.value).then_some(v.value.as_str())
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,21 @@
+mod context;
+mod expression;
+mod jsx_element;
+mod statement;
+
+use crate::ast;
+
+use self::context::Context;
+
+/// Prints `root_ast` back as Zac in the canonical style, keeping the comments
+/// of the `source` it was parsed from.
+pub fn format(root_ast: &ast::Root, source: &str) -> String {
+    let mut ctx = Context::new(source, &root_ast.comments);
+    let lines = statement::format_stmts(&mut ctx, &root_ast.stmts, source.len(), 0);
+
+    if lines.is_empty() {
+        "".to_string()
+    } else {
+        format!("{}\n", lines.join("\n"))
+    }
+}
@@ -0,0 +1,318 @@
+use crate::ast;
+
+use super::{
+    context::{fits, pad, Context},
+    expression,
+};
+
+pub fn format_stmts(
+    ctx: &mut Context,
+    stmts: &[ast::Stmt],
+    end: usize,
+    indent: usize,
+) -> Vec<String> {
+    let stmts = stmts
+        .iter()
+        .filter(|stmt| !matches!(stmt, ast::Stmt::Noop))
+        .collect::<Vec<_>>();
+
+    ctx.block(&stmts, end, indent, format)
+}
+
+pub fn format(ctx: &mut Context, stmt: &ast::Stmt, indent: usize) -> String {
+    match stmt {
+        ast::Stmt::Use(v) => format_use(v),
+        ast::Stmt::Import(v) => format_import(v),
+        ast::Stmt::Mod(v) => format_mod(ctx, v, indent),
+        ast::Stmt::Let(v) => format_let(ctx, v, indent),
+        ast::Stmt::Enum(v) => format_enum(ctx, v, indent),
+        ast::Stmt::Record(v) => format_record(ctx, v, indent),
+        ast::Stmt::Fn(v) if v.external => format!("extern {}", format_fn(ctx, v, indent)),
+        ast::Stmt::Fn(v) => format_fn(ctx, v, indent),
+        ast::Stmt::FnCall(v) => expression::format_fn_call(ctx, v, indent),
+        ast::Stmt::MemberAccess(v) => expression::format_member_access(ctx, v, indent),
+        ast::Stmt::Return(v) => format_return(ctx, v, indent),
+        ast::Stmt::LitJs(v) => expression::format_js(v),
+        ast::Stmt::If(v) => expression::format_if(ctx, v, indent, false),
+        ast::Stmt::Match(v) => expression::format_match(ctx, v, indent),
+        ast::Stmt::Noop | ast::Stmt::Error(_) => "".to_string(),
+    }
+}
+
+pub fn format_body(
+    ctx: &mut Context,
+    stmts: &[ast::FnStmt],
+    end: usize,
+    indent: usize,
+) -> Vec<String> {
+    let stmts = stmts
+        .iter()
+        .filter(|stmt| !matches!(stmt, ast::FnStmt::Noop))
+        .collect::<Vec<_>>();
+
+    ctx.block(&stmts, end, indent, format_fn_stmt)
+}
+
+pub fn format_fn_stmt(ctx: &mut Context, stmt: &ast::FnStmt, indent: usize) -> String {
+    match stmt {
+        ast::FnStmt::Let(v) => format_let(ctx, v, indent),
+        ast::FnStmt::FnCall(v) => expression::format_fn_call(ctx, v, indent),
+        ast::FnStmt::MemberAccess(v) => expression::format_member_access(ctx, v, indent),
+        ast::FnStmt::LitJs(v) => expression::format_js(v),
+        ast::FnStmt::If(v) => expression::format_if(ctx, v, indent, false),
+        ast::FnStmt::Match(v) => expression::format_match(ctx, v, indent),
+        ast::FnStmt::Ret(v) => format_return(ctx, v, indent),
+        ast::FnStmt::Expr(v) => expression::format(ctx, v, indent),
+        ast::FnStmt::Noop | ast::FnStmt::Error(_) => "".to_string(),
+    }
+}
+
+fn format_use(ast: &ast::Use) -> String {
+    match &ast.path {
+        ast::UsePath::Module(paths) => format!("use {}", paths.join(".")),
+        ast::UsePath::File(path) => format!("use \"{path}\""),
+    }
+}
+
+fn format_import(ast: &ast::Import) -> String {
+    let mut parts = vec![];
+
+    if let Some(default) = &ast.default {
+        parts.push(format_import_name(default));
+    }
+
+    if let Some(namespace) = &ast.namespace {
+        parts.push(format!("* as {}", namespace.id.string));
+    }
+
+    if !ast.names.is_empty() {
+        let names = ast.names.iter().map(format_import_name).collect::<Vec<_>>();
+
+        parts.push(format!("{{ {} }}", names.join(", ")));
+    }
+
+    if parts.is_empty() {
+        format!("import \"{}\"", ast.source)
+    } else {
+        format!("import {} from \"{}\"", parts.join(", "), ast.source)
+    }
+}
+
+fn format_import_name(name: &ast::ImportName) -> String {
+    let id = match &name.exported {
+        Some(exported) => format!("{} as {}", exported.string, name.id.string),
+        None => name.id.string.clone(),
+    };
+
+    match &name.ty {
+        ast::Type::Default => id,
+        ty => format!("{id}: {}", expression::format_type(ty)),
+    }
+}
+
+fn format_mod(ctx: &mut Context, module: &ast::Mod, indent: usize) -> String {
+    let end = ctx.code_end(&module.span).saturating_sub(1);
+
+    if !module.external {
+        let lines = format_stmts(ctx, &module.stmts, end, indent + 1);
+
+        return format!("mod {} {}", module.path, braced(&lines, indent));
+    }
+
+    // The fns of an extern mod are all extern, they go without the keyword.
+    let stmts = module
+        .stmts
+        .iter()
+        .filter(|stmt| !matches!(stmt, ast::Stmt::Noop))
+        .collect::<Vec<_>>();
+    let lines = ctx.block(&stmts, end, indent + 1, |ctx, stmt, indent| match stmt {
+        ast::Stmt::Fn(v) => format_fn(ctx, v, indent),
+        stmt => format(ctx, stmt, indent),
+    });
+
+    format!("extern mod {} {}", module.path, braced(&lines, indent))
+}
+
+fn format_let(ctx: &mut Context, ast: &ast::Let, indent: usize) -> String {
+    let ty = match &ast.ty {
+        ast::Type::Default => "".to_string(),
+        ty => format!(": {}", expression::format_type(ty)),
+    };
+
+    format!(
+        "{}let {}{ty} = {}",
+        visibility(ast.public),
+        ast.id,
+        expression::format(ctx, &ast.expr, indent)
+    )
+}
+
+fn format_record(ctx: &mut Context, record: &ast::Record, indent: usize) -> String {
+    let open = format!("record {} {{", expression::format_ident(&record.id));
+    let close_pos = ctx.code_end(&record.span).saturating_sub(1);
+
+    if !ctx.has_comments_before(close_pos) {
+        let props = record
+            .props
+            .iter()
+            .map(|prop| format_record_prop(ctx, prop, indent))
+            .collect::<Vec<_>>();
+        let inline = if props.is_empty() {
+            format!("{open}}}")
+        } else {
+            format!("{open} {} }}", props.join(", "))
+        };
+
+        if fits(indent, &inline) {
+            return inline;
+        }
+    }
+
+    expression::format_lines(
+        ctx,
+        &open,
+        "}",
+        &record.props,
+        close_pos,
+        indent,
+        format_record_prop,
+    )
+}
+
+fn format_record_prop(_ctx: &mut Context, prop: &ast::RecProp, _indent: usize) -> String {
+    format!(
+        "{}: {}",
+        prop.id.string,
+        expression::format_type(&prop.init)
+    )
+}
+
+fn format_enum(ctx: &mut Context, ast: &ast::Enum, indent: usize) -> String {
+    let open = format!(
+        "{}enum {} {{",
+        visibility(ast.public),
+        expression::format_ident(&ast.id)
+    );
+    let close_pos = ctx.code_end(&ast.span).saturating_sub(1);
+
+    if !ctx.has_comments_before(close_pos) {
+        let variants = ast
+            .variants
+            .iter()
+            .map(|variant| format_variant(ctx, variant, indent))
+            .collect::<Vec<_>>();
+        let inline = if variants.is_empty() {
+            format!("{open}}}")
+        } else {
+            format!("{open} {} }}", variants.join(", "))
+        };
+
+        if fits(indent, &inline) {
+            return inline;
+        }
+    }
+
+    expression::format_lines(
+        ctx,
+        &open,
+        "}",
+        &ast.variants,
+        close_pos,
+        indent,
+        format_variant,
+    )
+}
+
+fn format_variant(_ctx: &mut Context, variant: &ast::EnumVariant, _indent: usize) -> String {
+    if variant.fields.is_empty() {
+        return variant.id.string.clone();
+    }
+
+    let fields = variant
+        .fields
+        .iter()
+        .map(expression::format_type)
+        .collect::<Vec<_>>();
+
+    format!("{}({})", variant.id.string, fields.join(", "))
+}
+
+pub fn format_fn(ctx: &mut Context, ast: &ast::Fn, indent: usize) -> String {
+    let args = ast
+        .args
+        .iter()
+        .map(|arg| match &arg.input {
+            ast::Type::Default => arg.id.string.clone(),
+            input => format!("{}: {}", arg.id.string, expression::format_type(input)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let output = match &ast.output {
+        ast::Type::Default => "".to_string(),
+        output => format!(": {}", expression::format_type(output)),
+    };
+    let signature = if ast.anonymous {
+        format!("({args}){output}")
+    } else {
+        format!(
+            "{}fn {}({args}){output}",
+            visibility(ast.public),
+            expression::format_ident(&ast.id)
+        )
+    };
+
+    if ast.external {
+        return signature;
+    }
+
+    let end = ctx.code_end(&ast.span).saturating_sub(1);
+    let has_comments = ctx.has_comments_before(end);
+    let lines = format_body(ctx, &ast.stmts, end, indent + 1);
+
+    match lines.as_slice() {
+        [line] if ast.anonymous && !has_comments => {
+            let inline = format!("{signature} {{ {} }}", line.trim_start());
+
+            if fits(indent, &inline) {
+                return inline;
+            }
+
+            format!("{signature} {}", braced(&lines, indent))
+        }
+        _ => format!("{signature} {}", braced(&lines, indent)),
+    }
+}
+
+fn format_return(ctx: &mut Context, ast: &ast::Return, indent: usize) -> String {
+    match &ast.expr {
+        // Without the parentheses `return (a) { ... }` reads as returning `a`.
+        ast::Expr::Fn(v) => format!("return ({})", format_fn(ctx, v, indent)),
+        ast::Expr::JsxElement(_) => {
+            let element = expression::format(ctx, &ast.expr, indent + 1);
+
+            if element.contains('\n') {
+                format!("return\n{}{}", pad(indent + 1), element)
+            } else {
+                format!("return {element}")
+            }
+        }
+        expr => format!("return {}", expression::format(ctx, expr, indent)),
+    }
+}
+
+fn visibility(public: bool) -> &'static str {
+    if public {
+        "pub "
+    } else {
+        ""
+    }
+}
+
+/// Wraps already indented `lines` in braces closing at `indent`.
+pub fn braced(lines: &[String], indent: usize) -> String {
+    if lines.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{\n{}\n{}}}", lines.join("\n"), pad(indent))
+    }
+}
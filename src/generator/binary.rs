@@ -1,12 +1,36 @@
 use crate::ast;
 
-use super::context;
+use super::context::{self, GenResult};
 use super::expression;
 
-pub fn generate(ctx: &mut context::Context, v: &ast::Binary) -> String {
-    let op = v.op.value();
+pub fn generate(ctx: &mut context::Context, v: &ast::Binary) -> GenResult<String> {
+    if let Some(call) = v.piped_call() {
+        return expression::generate(ctx, &call);
+    }
 
-    let left = expression::generate(ctx, &v.left);
-    let right = expression::generate(ctx, &v.right);
-    return format!("{left} {op} {right}");
+    let op = v.op.js_value();
+    let precedence = v.op.precedence();
+
+    let left = operand(ctx, &v.left, precedence)?;
+    // Operators of the same precedence group to the left, `a - (b - c)` keeps
+    // its parentheses.
+    let right = operand(ctx, &v.right, precedence + 1)?;
+    Ok(format!("{left} {op} {right}"))
+}
+
+/// Generates `expr`, in parentheses when its operator binds looser than
+/// `min_precedence`.
+pub fn operand(
+    ctx: &mut context::Context,
+    expr: &ast::Expr,
+    min_precedence: u8,
+) -> GenResult<String> {
+    let code = expression::generate(ctx, expr)?;
+
+    match expr {
+        // A pipe is generated as a call, which needs no parentheses.
+        ast::Expr::Binary(v) if v.op == ast::BinaryOp::Pipe => Ok(code),
+        ast::Expr::Binary(v) if v.op.precedence() < min_precedence => Ok(format!("({code})")),
+        _ => Ok(code),
+    }
 }
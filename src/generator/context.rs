@@ -1,16 +1,15 @@
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 
-lazy_static! {
-    static ref LOWERCASED: Regex = Regex::new(r"([a-z]+)").unwrap();
-}
 use crate::{
     ast::{self, Type},
-    error_message::ErrorMessage,
+    checker::Methods,
+    diagnostic::{code, Diagnostic},
+    loader::UsedFiles,
     span::{Span, Spanned},
 };
 
+pub type GenResult<T> = Result<T, Diagnostic>;
+
 #[derive(Debug, Clone)]
 pub struct InferedType {
     pub id: String,
@@ -36,11 +35,11 @@ impl From<ast::Type> for InferedType {
     }
 }
 
-impl Into<ast::Ident> for InferedType {
-    fn into(self) -> ast::Ident {
+impl From<InferedType> for ast::Ident {
+    fn from(val: InferedType) -> Self {
         ast::Ident {
-            string: self.id.clone(),
-            generics: self
+            string: val.id.clone(),
+            generics: val
                 .generics
                 .iter()
                 .map(|g| ast::Ident {
@@ -62,7 +61,21 @@ pub struct Context {
     pub mod_defs: HashMap<String, ast::Mod>,
     pub fn_defs: HashMap<String, ast::Fn>,
     pub var_defs: HashMap<String, ast::Let>,
+    pub record_defs: HashMap<String, ast::Record>,
+    pub enum_defs: HashMap<String, ast::Enum>,
     pub resolved_type_defs: HashMap<String, InferedType>,
+    /// The definitions of the core library the file has not replaced with its
+    /// own, by the name they are registered with, along with the names they
+    /// are imported with.
+    pub prelude: HashMap<String, Vec<String>>,
+    /// The JS names of the extern fns, by the name they are registered with,
+    /// `Deno.readTextFile` for `Deno_readTextFile`.
+    pub extern_names: HashMap<String, String>,
+    /// The names brought in scope by an `import`, along with it.
+    pub import_defs: HashMap<String, ast::Import>,
+    /// The fns the checker resolved `value.name()` calls to.
+    pub methods: Methods,
+    pub used_files: Rc<UsedFiles>,
 }
 
 impl Context {
@@ -74,7 +87,14 @@ impl Context {
             fn_defs: HashMap::new(),
             mod_defs: HashMap::new(),
             var_defs: HashMap::new(),
+            record_defs: HashMap::new(),
+            enum_defs: HashMap::new(),
             resolved_type_defs: HashMap::new(),
+            prelude: HashMap::new(),
+            extern_names: HashMap::new(),
+            import_defs: HashMap::new(),
+            methods: Methods::new(),
+            used_files: Rc::new(UsedFiles::new()),
         }
     }
 
@@ -86,76 +106,180 @@ impl Context {
             fn_defs: self.fn_defs.clone(),
             mod_defs: self.mod_defs.clone(),
             var_defs: self.var_defs.clone(),
+            record_defs: self.record_defs.clone(),
+            enum_defs: self.enum_defs.clone(),
             resolved_type_defs: self.resolved_type_defs.clone(),
+            prelude: self.prelude.clone(),
+            extern_names: self.extern_names.clone(),
+            import_defs: self.import_defs.clone(),
+            methods: self.methods.clone(),
+            used_files: self.used_files.clone(),
         }
     }
 
-    pub fn throw_custom(&self, spanned: &dyn Spanned, message: &str) -> String {
-        let filepath = self.file_path.clone();
-        let content = self.file_content.clone();
-        let source_span = spanned.span();
+    pub fn error(&self, spanned: &dyn Spanned, message: &str) -> Diagnostic {
+        Diagnostic::error(self.file_path.clone(), message.to_string(), spanned.span())
+            .with_code(code::COMPILE_ERROR)
+    }
 
-        ErrorMessage::new(filepath, content, message.to_string(), source_span.from).print();
+    pub fn throw_custom<T>(&self, spanned: &dyn Spanned, message: &str) -> GenResult<T> {
+        Err(self.error(spanned, message))
+    }
 
-        panic!()
+    pub fn throw_already_defined<T>(
+        &self,
+        spanned: &dyn Spanned,
+        previous: &dyn Spanned,
+        message: &str,
+    ) -> GenResult<T> {
+        Err(self
+            .error(spanned, message)
+            .with_code(code::ALREADY_DEFINED)
+            .with_note("The previous definition is here.", Some(previous.span())))
     }
 
-    pub fn add_fn(&mut self, path: &String, ast_fn: &ast::Fn) -> &mut Self {
-        if !self.fn_defs.contains_key(path) {
-            self.fn_defs.insert(path.clone(), ast_fn.clone());
-            self
-        } else {
-            self.throw_custom(
+    pub fn add_fn(&mut self, path: &String, ast_fn: &ast::Fn) -> GenResult<&mut Self> {
+        // The file's own definitions replace the ones of the core library.
+        if self.prelude.remove(path).is_some() {
+            self.fn_defs.remove(path);
+            self.extern_names.remove(path);
+        }
+
+        match self.fn_defs.get(path) {
+            None => {
+                self.fn_defs.insert(path.clone(), ast_fn.clone());
+                Ok(self)
+            }
+            Some(previous) => self.throw_already_defined(
                 ast_fn,
+                previous,
                 &format!("The function {path} has already been defined previously."),
-            );
-            panic!();
+            ),
+        }
+    }
+
+    pub fn add_extern(
+        &mut self,
+        path: &String,
+        ast_fn: &ast::Fn,
+        js_name: String,
+    ) -> GenResult<&mut Self> {
+        self.add_fn(path, ast_fn)?;
+        self.extern_names.insert(path.clone(), js_name);
+        Ok(self)
+    }
+
+    pub fn add_import(
+        &mut self,
+        name: &ast::ImportName,
+        ast_import: &ast::Import,
+    ) -> GenResult<&mut Self> {
+        let path = &name.id.string;
+
+        match self.import_defs.get(path) {
+            None => {
+                self.import_defs.insert(path.clone(), ast_import.clone());
+                Ok(self)
+            }
+            Some(previous) => self.throw_already_defined(
+                name,
+                previous,
+                &format!("The name {path} has already been imported previously."),
+            ),
         }
     }
 
-    pub fn add_mod(&mut self, path: &String, ast_mod: &ast::Mod) -> &mut Self {
-        if !self.mod_defs.contains_key(path) {
-            self.mod_defs.insert(path.clone(), ast_mod.clone());
-            self
-        } else {
-            self.throw_custom(
+    /// Whether `name` is the module imported as a whole by `import * as name`.
+    pub fn is_namespace(&self, name: &str) -> bool {
+        self.import_defs
+            .get(name)
+            .and_then(|ast_import| ast_import.namespace.as_ref())
+            .is_some_and(|namespace| namespace.id.string == name)
+    }
+
+    pub fn add_mod(&mut self, path: &String, ast_mod: &ast::Mod) -> GenResult<&mut Self> {
+        if self.prelude.remove(path).is_some() {
+            self.mod_defs.remove(path);
+        }
+
+        match self.mod_defs.get(path) {
+            None => {
+                self.mod_defs.insert(path.clone(), ast_mod.clone());
+                Ok(self)
+            }
+            Some(previous) => self.throw_already_defined(
                 ast_mod,
-                &format!("The function {path} has already been defined previously."),
-            );
-            panic!();
+                previous,
+                &format!("The module {path} has already been defined previously."),
+            ),
         }
     }
 
-    pub fn add_var(&mut self, path: &String, ast_var: &ast::Let) -> &mut Self {
-        if !self.var_defs.contains_key(path) {
-            self.var_defs.insert(path.clone(), ast_var.clone());
-            self
-        } else {
-            self.throw_custom(
+    pub fn add_var(&mut self, path: &String, ast_var: &ast::Let) -> GenResult<&mut Self> {
+        match self.var_defs.get(path) {
+            None => {
+                self.var_defs.insert(path.clone(), ast_var.clone());
+                Ok(self)
+            }
+            Some(previous) => self.throw_already_defined(
                 ast_var,
+                previous,
                 &format!("The variable {path} has already been defined previously."),
-            );
-            panic!();
+            ),
         }
     }
 
-    pub fn add_resolved_type(
-        &mut self,
-        path: &String,
-        ast_resolved_type: &InferedType,
-    ) -> &mut Self {
+    pub fn add_record(&mut self, path: &String, ast_record: &ast::Record) -> GenResult<&mut Self> {
+        if self.prelude.remove(path).is_some() {
+            self.record_defs.remove(path);
+        }
+
+        match self.record_defs.get(path) {
+            None => {
+                self.record_defs.insert(path.clone(), ast_record.clone());
+                Ok(self)
+            }
+            Some(previous) => self.throw_already_defined(
+                ast_record,
+                previous,
+                &format!("The record {path} has already been defined previously."),
+            ),
+        }
+    }
+
+    pub fn add_enum(&mut self, path: &String, ast_enum: &ast::Enum) -> GenResult<&mut Self> {
+        if self.prelude.remove(path).is_some() {
+            self.enum_defs.remove(path);
+        }
+
+        match self.enum_defs.get(path) {
+            None => {
+                self.enum_defs.insert(path.clone(), ast_enum.clone());
+                Ok(self)
+            }
+            Some(previous) => self.throw_already_defined(
+                ast_enum,
+                previous,
+                &format!("The enum {path} has already been defined previously."),
+            ),
+        }
+    }
+
+    pub fn add_resolved_type(&mut self, path: &str, ast_resolved_type: &InferedType) -> &mut Self {
         self.resolved_type_defs
-            .insert(path.clone(), ast_resolved_type.clone());
+            .insert(path.to_string(), ast_resolved_type.clone());
         self
     }
 
+    /// The fn named `method_name` taking a value of `type_` first, or any
+    /// value when its first argument has one of its type parameters as type.
     pub fn find_method(
         &mut self,
         method_name: &String,
         type_: ast::Ident,
     ) -> Option<(&String, &ast::Fn)> {
         for (path, fn_def) in self.fn_defs.iter() {
-            if fn_def.args.len() < 1 {
+            if fn_def.args.is_empty() {
                 continue;
             }
 
@@ -164,7 +288,13 @@ impl Context {
             }
 
             if let Type::Ident(v) = &fn_def.args[0].input {
-                if v.string == type_.string || LOWERCASED.is_match(&v.string) {
+                let is_param = fn_def
+                    .id
+                    .generics
+                    .iter()
+                    .any(|param| param.string == v.string);
+
+                if v.string == type_.string || is_param {
                     return Some((path, fn_def));
                 } else {
                     continue;
@@ -197,16 +327,22 @@ impl Context {
         None
     }
 
-    pub fn find_var(&mut self, path: String) -> Option<(&String, &ast::Let)> {
-        for (path_, var_def) in self.var_defs.iter() {
-            if path_.starts_with(&path) {
-                return Some((path_, var_def));
-            }
+    pub fn merge(&mut self, other: &mut Context) -> &mut Self {
+        // The definitions of the core library `other` replaced are replaced
+        // here too.
+        let replaced = self
+            .prelude
+            .keys()
+            .filter(|path| !other.prelude.contains_key(*path))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for path in replaced {
+            self.prelude.remove(&path);
+            self.fn_defs.remove(&path);
+            self.mod_defs.remove(&path);
         }
 
-        None
-    }
-    pub fn merge(&mut self, other: &mut Context) -> &mut Self {
         for (path, fn_def) in &other.fn_defs {
             if !self.fn_defs.contains_key(path) {
                 self.fn_defs.insert(path.clone(), fn_def.clone());
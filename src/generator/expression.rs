@@ -1,25 +1,32 @@
 use crate::ast;
 
+use super::context::{self, GenResult};
+use super::literal_boolean;
 use super::{
-    binary, fn_call, identifier, init_array, jsx_element, literal_js, literal_number,
-    literal_string, member_access, statement_fn, statement_if,
+    binary, fn_call, identifier, init_array, init_record, jsx_element, literal_js, literal_number,
+    literal_string, member_access, source_map, statement_fn, statement_if, statement_match, unary,
+    variant,
 };
-use super::{context, literal_boolean};
 
-pub fn generate(ctx: &mut context::Context, ast: &ast::Expr) -> String {
-    match ast {
-        ast::Expr::Id(v) => identifier::generate(v),
+pub fn generate(ctx: &mut context::Context, ast: &ast::Expr) -> GenResult<String> {
+    let code = match ast {
+        ast::Expr::Id(v) => Ok(identifier::generate(v)),
         ast::Expr::Fn(v) => statement_fn::generate(ctx, v),
         ast::Expr::FnCall(v) => fn_call::generate(ctx, v),
         ast::Expr::Binary(v) => binary::generate(ctx, v),
-        ast::Expr::LitBoolean(v) => literal_boolean::generate(v),
-        ast::Expr::LitNumber(v) => literal_number::generate(v),
+        ast::Expr::Unary(v) => unary::generate(ctx, v),
+        ast::Expr::LitBoolean(v) => Ok(literal_boolean::generate(v)),
+        ast::Expr::LitNumber(v) => Ok(literal_number::generate(v)),
         ast::Expr::LitString(v) => literal_string::generate(ctx, v, false, false),
-        ast::Expr::LitJs(v) => literal_js::generate(ctx, v),
+        ast::Expr::LitJs(v) => Ok(literal_js::generate(ctx, v)),
         ast::Expr::JsxElement(v) => jsx_element::generate(ctx, v),
         ast::Expr::InitArray(v) => init_array::generate(ctx, v),
-        ast::Expr::InitRecord(v) => todo!(),
+        ast::Expr::InitRecord(v) => init_record::generate(ctx, v),
         ast::Expr::MemberAccess(v) => member_access::generate(ctx, v),
         ast::Expr::If(v) => statement_if::generate(ctx, v),
-    }
+        ast::Expr::Match(v) => statement_match::generate(ctx, v),
+        ast::Expr::Variant(v) => variant::generate(ctx, v),
+    }?;
+
+    Ok(source_map::mark(ast, code))
 }
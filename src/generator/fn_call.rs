@@ -1,23 +1,24 @@
 use crate::ast;
 
-use super::context;
+use super::context::{self, GenResult};
 use super::expression;
 
-pub fn generate(ctx: &mut context::Context, ast: &ast::FnCall) -> String {
-    format!(
+pub fn generate(ctx: &mut context::Context, ast: &ast::FnCall) -> GenResult<String> {
+    Ok(format!(
         "{}({})",
         generate_callee(&ast.id),
-        generate_args(ctx, &ast.args)
-    )
+        generate_args(ctx, &ast.args)?
+    ))
 }
 
 pub fn generate_callee(id: &ast::Ident) -> String {
     id.string.to_string()
 }
 
-pub fn generate_args(ctx: &mut context::Context, args: &Vec<ast::Expr>) -> String {
-    args.iter()
+pub fn generate_args(ctx: &mut context::Context, args: &[ast::Expr]) -> GenResult<String> {
+    Ok(args
+        .iter()
         .map(|arg| expression::generate(ctx, arg))
-        .collect::<Vec<String>>()
-        .join(", ")
+        .collect::<GenResult<Vec<String>>>()?
+        .join(", "))
 }
@@ -1,13 +1,13 @@
-use super::context;
+use super::context::{self, GenResult};
 use crate::{ast, generator::expression};
 
-pub fn generate(ctx: &mut context::Context, v: &ast::InitArray) -> String {
+pub fn generate(ctx: &mut context::Context, v: &ast::InitArray) -> GenResult<String> {
     let items = v
         .items
         .iter()
         .map(|x| expression::generate(ctx, x))
-        .collect::<Vec<_>>()
+        .collect::<GenResult<Vec<_>>>()?
         .join(",");
 
-    format!("[{items}]")
+    Ok(format!("[{items}]"))
 }
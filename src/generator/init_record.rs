@@ -0,0 +1,26 @@
+use super::context::{self, GenResult};
+use crate::{ast, generator::expression};
+
+/// Generates a plain object, the fields are checked against the record
+/// declaration by the checker.
+pub fn generate(ctx: &mut context::Context, v: &ast::InitRecord) -> GenResult<String> {
+    let props = v
+        .props
+        .iter()
+        .map(|prop| match prop {
+            ast::InitObjectProp::Key(id) => Ok(id.string.clone()),
+            ast::InitObjectProp::KeyValue(id, expr) => Ok(format!(
+                "{}: {}",
+                id.string,
+                expression::generate(ctx, expr)?
+            )),
+            ast::InitObjectProp::Spread(id) => Ok(format!("...{}", id.string)),
+        })
+        .collect::<GenResult<Vec<_>>>()?;
+
+    if props.is_empty() {
+        Ok("{}".into())
+    } else {
+        Ok(format!("{{ {} }}", props.join(", ")))
+    }
+}
@@ -1,51 +1,54 @@
 use crate::ast;
 use crate::generator::expression;
 
-use super::context;
+use super::context::{self, GenResult};
 use super::{jsx_element_attribute, literal_string};
 
-pub fn generate(ctx: &mut context::Context, ast: &ast::JsxElement) -> String {
+pub fn generate(ctx: &mut context::Context, ast: &ast::JsxElement) -> GenResult<String> {
     let name = ast.name.clone();
-    let attrs = generate_attrs(ctx, &ast.attrs);
+    let attrs = generate_attrs(ctx, &ast.attrs)?;
 
     if !ast.self_closing {
-        let children = generate_children(ctx, &ast.children);
-        format!("<{name}{attrs}>{children}</{name}>")
+        let children = generate_children(ctx, &ast.children)?;
+        Ok(format!("<{name}{attrs}>{children}</{name}>"))
     } else {
-        format!("<{name}{attrs} />")
+        Ok(format!("<{name}{attrs} />"))
     }
 }
 
-fn generate_children(ctx: &mut context::Context, children: &Vec<ast::Expr>) -> String {
+fn generate_children(ctx: &mut context::Context, children: &[ast::Expr]) -> GenResult<String> {
     let children = children
         .iter()
         .map(|child| match child {
             ast::Expr::JsxElement(v) => generate(ctx, v),
             ast::Expr::LitString(v) => literal_string::generate(ctx, v, true, false),
-            _ => format!("{{{}}}", expression::generate(ctx, child)),
+            _ => Ok(format!("{{{}}}", expression::generate(ctx, child)?)),
         })
-        .collect::<Vec<String>>()
+        .collect::<GenResult<Vec<String>>>()?
         .join("\n")
-        .split("\n")
+        .split('\n')
         .map(|x| format!("  {x}"))
         .collect::<Vec<String>>()
         .join("\n");
 
-    format!("\n{children}\n")
+    Ok(format!("\n{children}\n"))
 }
 
-fn generate_attrs(ctx: &mut context::Context, attrs: &Vec<ast::JsxElementAttribute>) -> String {
+fn generate_attrs(
+    ctx: &mut context::Context,
+    attrs: &[ast::JsxElementAttribute],
+) -> GenResult<String> {
     let attrs = attrs
         .iter()
         .map(|attr| jsx_element_attribute::generate(ctx, attr))
-        .collect::<Vec<String>>()
+        .collect::<GenResult<Vec<String>>>()?
         .join(" ")
         .trim()
         .to_string();
 
-    if attrs.len() > 0 {
-        format!(" {attrs}")
+    if !attrs.is_empty() {
+        Ok(format!(" {attrs}"))
     } else {
-        attrs
+        Ok(attrs)
     }
 }
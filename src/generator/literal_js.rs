@@ -1,14 +1,14 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use super::context;
+use super::{context, source_map};
 use crate::ast;
 
 lazy_static! {
     static ref VARIABLE_REPLACEMENT: Regex = Regex::new(r#"#\{([a-zA-Z0-9_]*)\}"#).unwrap();
 }
 
-pub fn generate(ctx: &mut context::Context, ast: &ast::LitJs) -> String {
+pub fn generate(_ctx: &mut context::Context, ast: &ast::LitJs) -> String {
     let mut final_value = ast.value.clone();
 
     for found in VARIABLE_REPLACEMENT.find_iter(&ast.value) {
@@ -19,5 +19,5 @@ pub fn generate(ctx: &mut context::Context, ast: &ast::LitJs) -> String {
         }
     }
 
-    final_value.to_string()
+    source_map::escape(&final_value)
 }
@@ -8,5 +8,5 @@ lazy_static! {
 }
 
 pub fn generate(ast: &ast::LitNumber) -> String {
-    format!("{}", ast.value.to_string())
+    format!("{}", ast.value)
 }
@@ -1,34 +1,40 @@
-use lazy_static::lazy_static;
-use regex::Regex;
-
-use super::context;
+use super::{
+    context::{self, GenResult},
+    expression, source_map,
+};
 use crate::ast;
 
-lazy_static! {
-    static ref VARIABLE_REPLACEMENT: Regex = Regex::new(r#"#\{(.*)\}"#).unwrap();
-}
-
 pub fn generate(
     ctx: &mut context::Context,
     ast: &ast::LitString,
     jsx: bool,
     jsx_attr: bool,
-) -> String {
-    let mut final_value = ast.value.clone();
+) -> GenResult<String> {
+    // JSX text and attributes take no escapes, text needing some goes through a template.
+    if !ast.interpolates() && !source_map::needs_escape(&ast.value) {
+        if jsx {
+            return Ok(ast.value.clone());
+        } else if jsx_attr {
+            return Ok(format!("\"{}\"", ast.value));
+        }
+    }
+
+    let mut template = String::new();
 
-    for found in VARIABLE_REPLACEMENT.find_iter(&ast.value) {
-        let to_replace = found.as_str();
-        if let Some(caps) = VARIABLE_REPLACEMENT.captures(to_replace) {
-            let variable = &format!("${{{}}}", &caps[1]);
-            final_value = final_value.replace(to_replace, variable);
+    for part in &ast.parts {
+        match part {
+            ast::StrPart::Text(v) => template.push_str(&source_map::escape(
+                &v.replace('`', "\\`").replace("${", "\\${"),
+            )),
+            ast::StrPart::Expr(v) => {
+                template.push_str(&format!("${{{}}}", expression::generate(ctx, v)?));
+            }
         }
     }
 
-    if jsx {
-        format!("{}", final_value.to_string())
-    } else if jsx_attr {
-        format!("\"{}\"", final_value.to_string())
+    if jsx || jsx_attr {
+        Ok(format!("{{`{template}`}}"))
     } else {
-        format!("`{}`", final_value.to_string())
+        Ok(format!("`{template}`"))
     }
 }
@@ -1,27 +1,50 @@
 use crate::{ast, generator::context::InferedType};
 
-use super::{context, fn_call};
+use super::{
+    context::{self, GenResult},
+    expression, fn_call, statement_fn, variant,
+};
+
+pub fn generate(ctx: &mut context::Context, ast: &ast::MemberAccess) -> GenResult<String> {
+    if let ast::Expr::Id(obj) = &ast.obj {
+        if let Some(enum_def) = ctx.enum_defs.get(&obj.string).cloned() {
+            match &ast.prop {
+                ast::Expr::Id(v) => return variant::generate_qualified(ctx, &enum_def, v, &[]),
+                ast::Expr::FnCall(v) => {
+                    return variant::generate_qualified(ctx, &enum_def, &v.id, &v.args)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let ast::Expr::Id(obj) = &ast.obj {
+        if ctx.is_namespace(&obj.string) {
+            if let Some(expr) = namespace_member(ctx, obj, &ast.prop) {
+                return expression::generate(ctx, &expr);
+            }
+        }
+    }
 
-pub fn generate(ctx: &mut context::Context, ast: &ast::MemberAccess) -> String {
     let mut results: Vec<(Vec<String>, ast::Expr, String)> = vec![];
 
     let mut result = "".to_string();
 
-    scan(ctx, ast, vec![], &mut results);
+    scan(ctx, ast, vec![], &mut results)?;
 
     let mut results2: Vec<(String, String, Option<InferedType>)> = vec![];
 
-    resolve_types(ctx, &mut results, &mut results2);
+    resolve_types(ctx, &results, &mut results2)?;
 
     let results = results
         .iter()
         .map(|(a, b, c)| (a.join("."), b, c))
         .collect::<Vec<_>>();
 
-    for (idx, (path, item, res)) in results.iter().enumerate() {
-        let (current_infered_path, current_infered_alternative_path, current_infered_type) =
-            &results2[idx];
+    // The mods the chain starts with, `Config` in `Config.name`.
+    let mut mods: Vec<String> = vec![];
 
+    for (idx, (path, item, res)) in results.iter().enumerate() {
         match item {
             ast::Expr::Id(v) => {
                 let string = v.string.to_string();
@@ -29,15 +52,29 @@ pub fn generate(ctx: &mut context::Context, ast: &ast::MemberAccess) -> String {
                 if string == "await" {
                     result = format!("(await {result})")
                 } else {
-                    if let Some(_) = ctx.find_mod(string.clone()) {
+                    let mod_path = mods.iter().chain([&string]).cloned().collect::<Vec<_>>();
+
+                    if ctx.find_mod(mod_path.join(".")).is_some() {
+                        mods.push(string);
                     } else {
                         if idx == 0 {
                             result = string;
+                        } else if !result.is_empty() {
+                            // A field of a record, which is a plain object, or a
+                            // property of a JS value.
+                            result = format!("{result}.{string}");
+                        } else if let Some(name) = mod_let(ctx, &mods, &string) {
+                            result = name;
+                        } else if let Some(name) = mod_fn(ctx, &mods, &string) {
+                            result = name;
                         } else {
-                            if let None = current_infered_type {
-                            } else {
-                                result = format!("{result}_{string}");
-                            }
+                            return ctx.throw_custom(
+                                *item,
+                                &format!(
+                                    "The mod {} has no function or variable {string}.",
+                                    mods.join(".")
+                                ),
+                            );
                         }
                     }
                 }
@@ -46,44 +83,72 @@ pub fn generate(ctx: &mut context::Context, ast: &ast::MemberAccess) -> String {
             ast::Expr::FnCall(v) => {
                 let gen_fn = res;
 
-                let (_, _, last_infered_type) = &results2[if idx > 0 { idx - 1 } else { idx }];
+                // A call starting the chain, `fetch(url).await`, has no receiver.
+                let last_infered_type = match idx {
+                    0 => &None,
+                    _ => &results2[idx - 1].2,
+                };
 
-                let mut resolved_method_name =
-                    if let Some(last_item_infered_type) = last_infered_type {
-                        let curr_fn_call_id = v.id.string.to_string();
-                        let last_item_infered_type_as_ident = last_item_infered_type.clone().into();
+                // The checker knows the type of receivers the generator does not,
+                // `let t = "abc"` makes `t.len()` call `String_len`.
+                let checked_method = ctx.methods.get(&v.span).cloned();
 
-                        if let Some((method_path, _)) =
-                            ctx.find_method(&curr_fn_call_id, last_item_infered_type_as_ident)
-                        {
-                            method_path.to_string()
-                        } else {
-                            curr_fn_call_id
-                        }
+                let mut resolved_method_name = if let Some(method_path) = &checked_method {
+                    method_path.clone()
+                } else if let Some(last_item_infered_type) = last_infered_type {
+                    let curr_fn_call_id = v.id.string.to_string();
+                    let last_item_infered_type_as_ident = last_item_infered_type.clone().into();
+
+                    if let Some((method_path, _)) =
+                        ctx.find_method(&curr_fn_call_id, last_item_infered_type_as_ident)
+                    {
+                        method_path.to_string()
                     } else {
-                        v.id.string.to_string()
-                    };
+                        curr_fn_call_id
+                    }
+                } else {
+                    v.id.string.to_string()
+                };
 
                 let path = path.replace(".", "_");
                 let alternative_resolved_method_name = resolved_method_name.replace(".", "_");
 
-                if let Some(_) = ctx.find_fn(format!("{path}_{resolved_method_name}")) {
+                if ctx
+                    .find_fn(format!("{path}_{resolved_method_name}"))
+                    .is_some()
+                {
                     resolved_method_name = format!("{path}_{resolved_method_name}");
                 }
 
-                if let Some(_) = ctx.find_fn(format!("{alternative_resolved_method_name}")) {
-                    resolved_method_name = format!("{alternative_resolved_method_name}");
+                if ctx
+                    .find_fn(alternative_resolved_method_name.to_string())
+                    .is_some()
+                {
+                    resolved_method_name = alternative_resolved_method_name.to_string();
+                }
+
+                // Without a type nor a fn to call, the receiver is a JS value and
+                // this one of its methods, `helmet.head.join("\n")`.
+                if idx > 0
+                    && last_infered_type.is_none()
+                    && checked_method.is_none()
+                    && !result.is_empty()
+                    && !ctx.extern_names.contains_key(&resolved_method_name)
+                    && ctx.find_fn(resolved_method_name.clone()).is_none()
+                {
+                    result = format!("{result}.{gen_fn}");
+                    continue;
                 }
 
                 let to_replace = format!("{}(", v.id.string);
                 let gen_fn_part = gen_fn.replace(&to_replace, "");
 
-                let result_gen_fn = if gen_fn_part.trim().len() > 0 {
+                let result_gen_fn = if !gen_fn_part.trim().is_empty() {
                     if gen_fn_part.trim() == ")" {
                         format!("{result}{gen_fn_part}")
                     } else {
-                        if let None = last_infered_type {
-                            format!("{gen_fn_part}")
+                        if last_infered_type.is_none() && checked_method.is_none() {
+                            gen_fn_part.to_string()
                         } else {
                             format!("{result}, {gen_fn_part}")
                         }
@@ -92,49 +157,86 @@ pub fn generate(ctx: &mut context::Context, ast: &ast::MemberAccess) -> String {
                     result
                 };
 
-                result = format!("{resolved_method_name}({result_gen_fn}");
-                println!("result {result}");
+                let callee = ctx
+                    .extern_names
+                    .get(&resolved_method_name)
+                    .cloned()
+                    .unwrap_or(resolved_method_name);
+
+                result = format!("{callee}({result_gen_fn}");
             }
-            _ => panic!("Unsupported"),
+            _ => return ctx.throw_custom(*item, "Unsupported expression in member access."),
         }
     }
 
-    return result;
+    Ok(result)
+}
+
+/// The member of the module imported as `namespace` as a single expression,
+/// `path.join(a, b)` calls `path.join`. The fns an `extern mod` declares for
+/// it resolve like the ones of any mod instead.
+fn namespace_member(
+    ctx: &context::Context,
+    namespace: &ast::Ident,
+    prop: &ast::Expr,
+) -> Option<ast::Expr> {
+    let qualify = |id: &ast::Ident| ast::Ident {
+        string: format!("{}.{}", namespace.string, id.string),
+        ..id.clone()
+    };
+
+    match prop {
+        ast::Expr::FnCall(v)
+            if !ctx
+                .fn_defs
+                .contains_key(&format!("{}_{}", namespace.string, v.id.string)) =>
+        {
+            Some(ast::Expr::FnCall(ast::FnCall {
+                id: qualify(&v.id),
+                ..v.clone()
+            }))
+        }
+        ast::Expr::Id(v) => Some(ast::Expr::Id(qualify(v))),
+        ast::Expr::MemberAccess(v) => namespace_member(ctx, namespace, &v.obj).map(|obj| {
+            ast::Expr::MemberAccess(Box::new(ast::MemberAccess {
+                obj,
+                ..(**v).clone()
+            }))
+        }),
+        _ => None,
+    }
 }
 
 fn resolve_types(
     ctx: &mut context::Context,
-    results: &mut Vec<(Vec<String>, ast::Expr, String)>,
+    results: &[(Vec<String>, ast::Expr, String)],
     results2: &mut Vec<(String, String, Option<InferedType>)>,
-) {
-    for (idx, (path, item, res)) in results.iter().enumerate() {
-        let path1 = path.clone().join(".");
-
+) -> GenResult<()> {
+    for (idx, (path, item, _res)) in results.iter().enumerate() {
         let path2 = path.join("_");
 
         let id = match item {
             ast::Expr::Id(v) => {
                 if v.string == "await" {
-                    v.string.clone().into()
+                    v.string.clone()
                 } else {
                     "".into()
                 }
             }
             ast::Expr::FnCall(v) => v.id.string.to_string(),
-            _ => panic!("Unsupported"),
+            _ => return ctx.throw_custom(item, "Unsupported expression in member access."),
         };
 
-        let test = if path2.len() > 0 && id.len() > 0 {
+        let test = if !path2.is_empty() && !id.is_empty() {
             format!("{path2}_{id}")
-        } else if path2.len() == 0 && id.len() > 0 {
-            let id = id.replace(".", "_");
-            format!("{id}")
+        } else if path2.is_empty() && !id.is_empty() {
+            id.replace('.', "_")
         } else {
             format!("{path2}{id}")
         };
 
         let resolved_type: Option<InferedType> = if id == "await" {
-            let (prev_path, prev_alt_path, prev_item) = &results2[idx - 1];
+            let (_prev_path, _prev_alt_path, prev_item) = &results2[idx - 1];
 
             if let Some(prev_item) = prev_item {
                 if prev_item.id == "Promise" {
@@ -148,30 +250,74 @@ fn resolve_types(
             } else {
                 None
             }
+        } else if let (ast::Expr::Id(v), true) = (item, idx > 0) {
+            results2[idx - 1]
+                .2
+                .as_ref()
+                .and_then(|receiver| field_type(ctx, receiver, &v.string))
+        } else if let Some(fn_def) = checked_method(ctx, item) {
+            Some(statement_fn::output_type(fn_def))
         } else if let Some(fn_def) = ctx.fn_defs.get(&test) {
-            Some(fn_def.output.clone().into())
+            Some(statement_fn::output_type(fn_def))
         } else if let Some(var_def) = ctx.resolved_type_defs.get(&test) {
             Some(var_def.clone())
         } else if let Some(var_def) = ctx.resolved_type_defs.get(&id) {
             Some(var_def.clone())
-        } else if let Some((prev_path, prev_alt_path, prev_item)) =
+        } else if let Some((_prev_path, _prev_alt_path, prev_item)) =
             &results2.get(if idx > 0 { idx - 1 } else { idx })
         {
-            if let Some(prev_item) = prev_item {
-                if let Some((path, method)) = ctx.find_method(&id, prev_item.clone().into()) {
-                    Some(method.output.clone().into())
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
+            prev_item.as_ref().and_then(|prev_item| {
+                ctx.find_method(&id, prev_item.clone().into())
+                    .map(|(_, method)| statement_fn::output_type(method))
+            })
         } else {
             None
         };
 
         results2.push((test, id, resolved_type));
     }
+
+    Ok(())
+}
+
+/// The type of the field `name` of `receiver`, when it is a record.
+fn field_type(ctx: &context::Context, receiver: &InferedType, name: &str) -> Option<InferedType> {
+    ctx.record_defs
+        .get(&receiver.id)?
+        .props
+        .iter()
+        .find(|prop| prop.id.string == name)
+        .map(|prop| prop.init.clone().into())
+}
+
+/// The name the let `name` of the mods `mods` is generated with, `M_x` for `M.x`.
+fn mod_let(ctx: &context::Context, mods: &[String], name: &str) -> Option<String> {
+    let var_path = format!("{}_{name}", mods.join("_"));
+
+    ctx.var_defs.contains_key(&var_path).then_some(var_path)
+}
+
+/// The name the fn `name` of the mods `mods` is generated with when it is
+/// passed as a value, `M_inc` for `M.inc`.
+fn mod_fn(ctx: &context::Context, mods: &[String], name: &str) -> Option<String> {
+    let fn_path = format!("{}_{name}", mods.join("_"));
+
+    if !ctx.fn_defs.contains_key(&fn_path) {
+        return None;
+    }
+
+    Some(ctx.extern_names.get(&fn_path).cloned().unwrap_or(fn_path))
+}
+
+/// The fn the checker resolved `item`, a call like `t.len()`, to.
+fn checked_method<'a>(ctx: &'a context::Context, item: &ast::Expr) -> Option<&'a ast::Fn> {
+    match item {
+        ast::Expr::FnCall(v) => ctx
+            .methods
+            .get(&v.span)
+            .and_then(|method_path| ctx.fn_defs.get(method_path)),
+        _ => None,
+    }
 }
 
 fn scan(
@@ -179,7 +325,7 @@ fn scan(
     ast: &ast::MemberAccess,
     path: Vec<String>,
     results: &mut Vec<(Vec<String>, ast::Expr, String)>,
-) {
+) -> GenResult<()> {
     let mut path = path;
 
     let obj = match &ast.obj {
@@ -187,30 +333,32 @@ fn scan(
             if v.string != "await" {
                 path = path
                     .into_iter()
-                    .chain(vec![v.string.clone()].into_iter())
+                    .chain(vec![v.string.clone()])
                     .collect::<Vec<_>>();
             }
             v.string.to_string()
         }
-        ast::Expr::FnCall(v) => format!("{}", fn_call::generate(ctx, v)),
-        _ => panic!("Unsupported"),
+        ast::Expr::FnCall(v) => fn_call::generate(ctx, v)?,
+        _ => return ctx.throw_custom(&ast.obj, "Unsupported expression in member access."),
     };
 
     let prop = match &ast.prop {
         ast::Expr::Id(v) => v.string.to_string(),
-        ast::Expr::FnCall(v) => format!("{}", fn_call::generate(ctx, v)),
+        ast::Expr::FnCall(v) => fn_call::generate(ctx, v)?,
         ast::Expr::MemberAccess(v) => {
-            scan(ctx, v, path.clone(), results);
+            scan(ctx, v, path.clone(), results)?;
             "".into()
         }
-        _ => panic!("Unsupported"),
+        _ => return ctx.throw_custom(&ast.prop, "Unsupported expression in member access."),
     };
 
-    if prop.len() > 0 {
+    if !prop.is_empty() {
         results.insert(0, (path.clone(), ast.prop.clone(), prop));
     }
 
-    if obj.len() > 0 {
+    if !obj.is_empty() {
         results.insert(0, (path.clone(), ast.obj.clone(), obj));
     }
+
+    Ok(())
 }
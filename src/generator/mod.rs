@@ -4,35 +4,109 @@ mod expression;
 mod fn_call;
 mod identifier;
 mod init_array;
+mod init_record;
 mod jsx_element;
 mod jsx_element_attribute;
+mod literal_boolean;
 mod literal_js;
 mod literal_number;
 mod literal_string;
 mod member_access;
+mod output_paths;
 mod root;
+mod source_map;
+mod statement_enum;
 mod statement_fn;
 mod statement_if;
+mod statement_import;
 mod statement_let;
+mod statement_match;
 mod statement_mod;
 mod statement_return;
-mod literal_boolean;
+mod statement_use;
+mod unary;
+mod variant;
+
+use std::{
+    fs, io,
+    path::{self, Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::{ast, checker::Methods, diagnostic::Diagnostic, loader::UsedFiles, utils};
+
+pub use self::output_paths::OutputPaths;
+pub use self::source_map::{Mapping, SourceMap};
+pub(crate) use self::statement_use::resolve as resolve_use;
+
+/// The core library known to the generated file.
+pub struct Prelude<'a> {
+    pub root_ast: &'a ast::Root,
+    /// The specifier the file imports the core library with.
+    pub specifier: &'a str,
+}
+
+/// Generates the JSX for `root_ast` along with a source map pointing back into
+/// `file_content`. `methods` are the fns the checker resolved method calls to
+/// and `used_files` the files the `use` statements refer to.
+pub fn generate(
+    file_path: String,
+    file_content: String,
+    root_ast: &ast::Root,
+    prelude: Option<Prelude>,
+    methods: Methods,
+    used_files: Rc<UsedFiles>,
+) -> Result<(String, SourceMap), Diagnostic> {
+    let mut ctx = context::Context::new(file_path, file_content.clone());
 
-use std::{fs, path::Path};
+    ctx.methods = methods;
+    ctx.used_files = used_files;
+
+    let marked = root::generate(&mut ctx, root_ast, prelude)?;
+
+    Ok(source_map::extract(&marked, &file_content))
+}
+
+/// Writes the generated core library to the root of the output directory.
+pub fn write_prelude(output_paths: &OutputPaths, content: &str) -> io::Result<PathBuf> {
+    let target_file_path = output_paths.prelude_file_path()?;
+
+    fs::create_dir_all(target_file_path.parent().unwrap_or(Path::new("/")))?;
+    fs::write(&target_file_path, format!("{content}\n"))?;
+
+    Ok(target_file_path)
+}
 
-use crate::{ast, utils::normalize_path};
+/// Writes the generated `content` to its place in the output directory, with
+/// the source map next to it as `<name>.jsx.map`.
+pub fn write(
+    output_paths: &OutputPaths,
+    file_path: &str,
+    content: &str,
+    source_map: &SourceMap,
+) -> io::Result<PathBuf> {
+    let target_file_path = output_paths.target_file_path(Path::new(file_path))?;
+    let target_folder_path = target_file_path.parent().unwrap_or(Path::new("/"));
+    let file_name = target_file_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let map_file_name = format!("{file_name}.map");
+    let source_path = utils::normalize_path(&path::absolute(file_path)?);
+    let relative_source_path = utils::relative_path(target_folder_path, &source_path)
+        .to_string_lossy()
+        .replace('\\', "/");
 
-pub fn generate(file_path: String, file_content: String, root_ast: &ast::Root) {
-    let source_folder_path = Path::new(&file_path);
-    let source_file_name = source_folder_path.file_name().unwrap().to_str().unwrap();
-    let target_folder_path = &file_path.replace("samples", "build");
-    let target_folder_path = normalize_path(&Path::new(target_folder_path).join(".."));
-    let target_file_path = target_folder_path.join(source_file_name.replace(".zac", ".jsx"));
+    fs::create_dir_all(target_folder_path)?;
+    fs::write(
+        &target_file_path,
+        format!("{content}\n//# sourceMappingURL={map_file_name}\n"),
+    )?;
+    fs::write(
+        target_folder_path.join(&map_file_name),
+        source_map.to_json(&file_name, &relative_source_path),
+    )?;
 
-    let _ = fs::create_dir_all(&target_folder_path);
-    let content = root::generate(
-        &mut context::Context::new(file_path, file_content),
-        root_ast,
-    );
-    let _ = fs::write(target_file_path, content);
+    Ok(target_file_path)
 }
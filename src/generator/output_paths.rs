@@ -0,0 +1,93 @@
+use std::{
+    io,
+    path::{self, Path, PathBuf},
+};
+
+use crate::{
+    prelude,
+    utils::{normalize_path, relative_path},
+};
+
+#[derive(Debug, Clone)]
+pub struct OutputPaths {
+    pub source_root: PathBuf,
+    pub out_dir: PathBuf,
+    pub out_file: Option<PathBuf>,
+}
+
+impl OutputPaths {
+    pub fn new(source_root: PathBuf, out_dir: PathBuf) -> OutputPaths {
+        OutputPaths {
+            source_root,
+            out_dir,
+            out_file: None,
+        }
+    }
+
+    pub fn with_out_file(&self, out_file: PathBuf) -> OutputPaths {
+        OutputPaths {
+            source_root: self.source_root.clone(),
+            out_dir: self.out_dir.clone(),
+            out_file: Some(out_file),
+        }
+    }
+
+    pub fn target_file_path(&self, file_path: &Path) -> io::Result<PathBuf> {
+        let source_root = absolute(&self.source_root)?;
+        let out_dir = absolute(&self.out_dir)?;
+
+        let relative_path = match &self.out_file {
+            Some(out_file) => out_file.clone(),
+            None => absolute(file_path)?
+                .strip_prefix(&source_root)
+                .map_err(|_| {
+                    invalid_input(format!(
+                        "not inside the source root {}",
+                        self.source_root.display()
+                    ))
+                })?
+                .with_extension("jsx"),
+        };
+
+        let target_file_path = normalize_path(&out_dir.join(relative_path));
+
+        if !target_file_path.starts_with(&out_dir) || target_file_path == out_dir {
+            return Err(invalid_input(format!(
+                "refusing to write {} outside of the output directory {}",
+                target_file_path.display(),
+                self.out_dir.display()
+            )));
+        }
+
+        Ok(target_file_path)
+    }
+
+    /// Where the core library is written, once for every generated file.
+    pub fn prelude_file_path(&self) -> io::Result<PathBuf> {
+        Ok(absolute(&self.out_dir)?.join(prelude::FILE_NAME))
+    }
+
+    /// The specifier the file generated for `file_path` imports the core
+    /// library with.
+    pub fn prelude_specifier(&self, file_path: &Path) -> io::Result<String> {
+        let target_file_path = self.target_file_path(file_path)?;
+        let target_folder_path = target_file_path.parent().unwrap_or(Path::new("/"));
+        let specifier = relative_path(target_folder_path, &self.prelude_file_path()?)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        if specifier.starts_with("../") {
+            Ok(specifier)
+        } else {
+            Ok(format!("./{specifier}"))
+        }
+    }
+}
+
+fn absolute(path: &Path) -> io::Result<PathBuf> {
+    Ok(normalize_path(&path::absolute(path)?))
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
@@ -1,35 +1,116 @@
-use crate::ast;
+use std::collections::HashSet;
+
+use crate::{ast, span::Span};
 
 use super::{
-    context, fn_call, literal_js, member_access, statement_fn, statement_let, statement_mod,
-    statement_return,
+    context::{self, GenResult},
+    fn_call, literal_js, member_access, source_map, statement_enum, statement_fn, statement_if,
+    statement_import, statement_let, statement_match, statement_mod, statement_return,
+    statement_use, Prelude,
 };
 
-pub fn generate(ctx: &mut context::Context, root_ast: &ast::Root) -> String {
-    generate_statements(ctx, &root_ast.stmts)
+pub fn generate(
+    ctx: &mut context::Context,
+    root_ast: &ast::Root,
+    prelude: Option<Prelude>,
+) -> GenResult<String> {
+    if let Some(prelude) = &prelude {
+        ctx.prelude = statement_use::register(ctx, &Span::empty(), prelude.root_ast)?
+            .into_iter()
+            .collect();
+    }
+
+    // Records, enums, externs and imports can be used before they are
+    // declared, records and externs generate no code at all.
+    for stmt in &root_ast.stmts {
+        match stmt {
+            ast::Stmt::Import(v) => {
+                statement_import::register(ctx, v)?;
+            }
+            ast::Stmt::Fn(v) if v.external => {
+                ctx.add_extern(&v.id.string, v, v.id.string.clone())?;
+            }
+            ast::Stmt::Mod(v) if v.external => {
+                statement_mod::register_extern(ctx, v)?;
+            }
+            ast::Stmt::Record(v) => {
+                ctx.add_record(&v.id.string, v)?;
+            }
+            ast::Stmt::Enum(v) => {
+                ctx.add_enum(&v.id.string, v)?;
+            }
+            _ => {}
+        }
+    }
+
+    let code = generate_statements(ctx, &root_ast.stmts)?
         .join("\n\n")
         .trim()
-        .to_string()
+        .to_string();
+
+    match prelude {
+        Some(prelude) => Ok(import_prelude(ctx, &code, prelude.specifier)),
+        None => Ok(code),
+    }
+}
+
+/// `code` importing the fns and enum constructors of the core library it
+/// uses.
+fn import_prelude(ctx: &context::Context, code: &str, specifier: &str) -> String {
+    let words = code
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .collect::<HashSet<_>>();
+    let mut names = ctx
+        .prelude
+        .values()
+        .flatten()
+        .filter(|name| words.contains(name.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        return code.to_string();
+    }
+
+    names.sort();
+
+    format!(
+        "import {{ {} }} from \"{specifier}\"\n\n{code}",
+        names.join(", ")
+    )
 }
 
-fn generate_statements(ctx: &mut context::Context, stmts: &Vec<ast::Stmt>) -> Vec<String> {
+fn generate_statements(ctx: &mut context::Context, stmts: &[ast::Stmt]) -> GenResult<Vec<String>> {
     stmts
         .iter()
-        .filter(|stmt| match stmt {
-            ast::Stmt::Noop => false,
-            _ => true,
+        .filter(|stmt| {
+            !matches!(
+                stmt,
+                ast::Stmt::Noop
+                    | ast::Stmt::Record(_)
+                    | ast::Stmt::Fn(ast::Fn { external: true, .. })
+                    | ast::Stmt::Mod(ast::Mod { external: true, .. })
+            )
         })
-        .map(|stmt| match stmt {
-            ast::Stmt::Mod(v) => statement_mod::generate(ctx, v),
-            ast::Stmt::Let(v) => statement_let::generate(ctx, v),
-            ast::Stmt::Record(v) => "record_todo".into(),
-            ast::Stmt::Fn(v) => statement_fn::generate(ctx, v),
-            ast::Stmt::FnCall(v) => fn_call::generate(ctx, v),
-            ast::Stmt::MemberAccess(v) => member_access::generate(ctx, v),
-            ast::Stmt::Return(v) => statement_return::generate(ctx, v),
-            ast::Stmt::LitJs(v) => literal_js::generate(ctx, v),
-            ast::Stmt::Noop => "".into(),
-            _ => panic!("Unexpected AST Node {:#?}", stmt),
+        .map(|stmt| {
+            let code = match stmt {
+                ast::Stmt::Use(v) => statement_use::generate(ctx, v),
+                ast::Stmt::Import(v) => Ok(statement_import::generate(v)),
+                ast::Stmt::Mod(v) => statement_mod::generate(ctx, v),
+                ast::Stmt::Let(v) => statement_let::generate(ctx, v),
+                ast::Stmt::Fn(v) => statement_fn::generate(ctx, v),
+                ast::Stmt::Enum(v) => Ok(statement_enum::generate(v)),
+                ast::Stmt::FnCall(v) => fn_call::generate(ctx, v),
+                ast::Stmt::MemberAccess(v) => member_access::generate(ctx, v),
+                ast::Stmt::Return(v) => statement_return::generate(ctx, v),
+                ast::Stmt::LitJs(v) => Ok(literal_js::generate(ctx, v)),
+                ast::Stmt::If(v) => statement_if::generate_statement(ctx, v),
+                ast::Stmt::Match(v) => statement_match::generate_statement(ctx, v),
+                ast::Stmt::Noop => Ok("".into()),
+                _ => ctx.throw_custom(stmt, "Unsupported statement while generating file."),
+            }?;
+
+            Ok(source_map::mark(stmt, code))
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
@@ -0,0 +1,185 @@
+use serde_json::json;
+
+use crate::span::Spanned;
+
+const MARK_START: char = '\u{E000}';
+const MARK_END: char = '\u{E001}';
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Maps positions in the generated code back to the source file, counting
+/// lines from zero and columns in UTF-16 code units.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    pub mappings: Vec<Mapping>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub generated_line: usize,
+    pub generated_column: usize,
+    pub source_line: usize,
+    pub source_column: usize,
+}
+
+impl SourceMap {
+    /// Serializes the map in the source map v3 format, `file` being the name of
+    /// the generated file and `source` the path of the source relative to it.
+    pub fn to_json(&self, file: &str, source: &str) -> String {
+        json!({
+            "version": 3,
+            "file": file,
+            "sources": [source],
+            "names": [],
+            "mappings": self.encode_mappings(),
+        })
+        .to_string()
+    }
+
+    fn encode_mappings(&self) -> String {
+        let mut result = String::new();
+        let mut line = 0;
+        let mut previous_column = 0;
+        let mut previous_source_line = 0;
+        let mut previous_source_column = 0;
+
+        for (idx, mapping) in self.mappings.iter().enumerate() {
+            if mapping.generated_line > line {
+                result.push_str(&";".repeat(mapping.generated_line - line));
+                line = mapping.generated_line;
+                previous_column = 0;
+            } else if idx > 0 {
+                result.push(',');
+            }
+
+            encode_vlq(
+                &mut result,
+                delta(mapping.generated_column, previous_column),
+            );
+            encode_vlq(&mut result, 0);
+            encode_vlq(
+                &mut result,
+                delta(mapping.source_line, previous_source_line),
+            );
+            encode_vlq(
+                &mut result,
+                delta(mapping.source_column, previous_source_column),
+            );
+
+            previous_column = mapping.generated_column;
+            previous_source_line = mapping.source_line;
+            previous_source_column = mapping.source_column;
+        }
+
+        result
+    }
+}
+
+/// Tags `code` with the start of the span it was generated from. The tags are
+/// stripped again by `extract` once the whole file has been generated.
+pub(crate) fn mark(spanned: &dyn Spanned, code: String) -> String {
+    if code.is_empty() {
+        return code;
+    }
+
+    format!("{MARK_START}{}{MARK_END}{code}", spanned.span().from)
+}
+
+/// Escapes the characters `mark` tags code with in `text`, copied from the
+/// source into a JS string, template or regex where `\uE000` reads the same.
+/// Left as they are, they would be taken for tags by `extract`.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace(MARK_START, "\\uE000")
+        .replace(MARK_END, "\\uE001")
+}
+
+/// Whether `text` contains characters `escape` has to escape.
+pub(crate) fn needs_escape(text: &str) -> bool {
+    text.contains([MARK_START, MARK_END])
+}
+
+/// Removes the tags left by `mark` from `marked`, returning the final code and
+/// the mappings they describe. `source` is the content of the source file.
+pub(crate) fn extract(marked: &str, source: &str) -> (String, SourceMap) {
+    let line_starts = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect::<Vec<_>>();
+    let mut code = String::with_capacity(marked.len());
+    let mut mappings: Vec<Mapping> = vec![];
+    let mut generated_line = 0;
+    let mut generated_column = 0;
+    let mut chars = marked.chars();
+
+    while let Some(character) = chars.next() {
+        match character {
+            MARK_START => {
+                let pos = chars
+                    .by_ref()
+                    .take_while(|character| *character != MARK_END)
+                    .collect::<String>()
+                    .parse::<usize>()
+                    .unwrap_or_default()
+                    .min(source.len());
+                let source_line = line_starts.partition_point(|start| *start <= pos) - 1;
+                let line_start = line_starts[source_line];
+                let source_column = source
+                    .get(line_start..pos)
+                    .map_or(0, |before| before.encode_utf16().count());
+                let mapping = Mapping {
+                    generated_line,
+                    generated_column,
+                    source_line,
+                    source_column,
+                };
+
+                // Nested nodes often start at the same output position, keep the innermost.
+                match mappings.last_mut() {
+                    Some(last)
+                        if last.generated_line == generated_line
+                            && last.generated_column == generated_column =>
+                    {
+                        *last = mapping
+                    }
+                    _ => mappings.push(mapping),
+                }
+            }
+            '\n' => {
+                code.push(character);
+                generated_line += 1;
+                generated_column = 0;
+            }
+            _ => {
+                code.push(character);
+                generated_column += character.len_utf16();
+            }
+        }
+    }
+
+    (code, SourceMap { mappings })
+}
+
+fn delta(value: usize, previous: usize) -> i64 {
+    value as i64 - previous as i64
+}
+
+fn encode_vlq(result: &mut String, value: i64) {
+    let mut vlq = if value < 0 {
+        (-value << 1) | 1
+    } else {
+        value << 1
+    };
+
+    loop {
+        let mut digit = vlq & 31;
+        vlq >>= 5;
+
+        if vlq > 0 {
+            digit |= 32;
+        }
+
+        result.push(BASE64[digit as usize] as char);
+
+        if vlq == 0 {
+            break;
+        }
+    }
+}
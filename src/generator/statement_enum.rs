@@ -0,0 +1,49 @@
+use crate::ast;
+
+/// Every variant gets a constructor building a tagged object, `{ $tag: "Some",
+/// 0: value }`. Variants without values are plain constants. The
+/// constructors of a `pub enum` are exported.
+pub fn generate(ast: &ast::Enum) -> String {
+    ast.variants
+        .iter()
+        .map(|variant| generate_variant(ast, variant))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn generate_variant(ast: &ast::Enum, variant: &ast::EnumVariant) -> String {
+    let name = constructor_name(&ast.id.string, &variant.id.string);
+    let export = if ast.public { "export " } else { "" };
+    let tag = format!("$tag: \"{}\"", variant.id.string);
+
+    if variant.fields.is_empty() {
+        return format!("{export}const {name} = {{ {tag} }}");
+    }
+
+    let args = (0..variant.fields.len())
+        .map(|idx| format!("_{idx}"))
+        .collect::<Vec<_>>();
+    let props = args
+        .iter()
+        .enumerate()
+        .map(|(idx, arg)| format!(", {idx}: {arg}"))
+        .collect::<String>();
+
+    format!(
+        "{export}function {name}({}) {{\n  return {{ {tag}{props} }}\n}}",
+        args.join(", ")
+    )
+}
+
+/// The name `Variant` of the enum `Enum` is generated with.
+pub fn constructor_name(enum_name: &str, variant_name: &str) -> String {
+    format!("{enum_name}_{variant_name}")
+}
+
+/// The names of the constructors of `ast`, in declaration order.
+pub fn constructor_names(ast: &ast::Enum) -> Vec<String> {
+    ast.variants
+        .iter()
+        .map(|variant| constructor_name(&ast.id.string, &variant.id.string))
+        .collect()
+}
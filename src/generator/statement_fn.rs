@@ -1,32 +1,59 @@
 use crate::ast::{self};
 
-use super::{fn_call, literal_js, member_access, statement_if, statement_let, statement_return};
+use super::{
+    expression, fn_call, literal_js, member_access, source_map, statement_if, statement_let,
+    statement_match, statement_return,
+};
+
+use super::context::{self, GenResult, InferedType};
+pub fn generate(ctx: &mut context::Context, ast: &ast::Fn) -> GenResult<String> {
+    if ast.external {
+        return ctx.throw_custom(
+            ast,
+            "An extern fn can only be declared at the top level of a file or in an extern mod.",
+        );
+    }
 
-use super::context;
-pub fn generate(ctx: &mut context::Context, ast: &ast::Fn) -> String {
     let id_ = ast.id.string.clone();
 
-    let id = if ctx.module_path.len() > 0 {
+    let id = if !ctx.module_path.is_empty() {
         format!("{}_{}", ctx.module_path, id_)
     } else {
         id_
     };
 
-    ctx.add_fn(&id, ast);
+    ctx.add_fn(&id, ast)?;
 
     let args = generate_args(ctx, &ast.args);
-    let stmts = generate_body(ctx, &ast.stmts);
+    let stmts = generate_body(ctx, &ast.stmts)?;
+
+    let async_ = if ast.is_async { "async " } else { "" };
 
     if !ast.anonymous {
-        format!("function {}({}) {{{}}}", id, args, stmts,)
+        let export = if ast.public { "export " } else { "" };
+
+        Ok(format!("{export}{async_}function {id}({args}) {{{stmts}}}"))
     } else {
-        format!("({}) => {{{}}}", args, stmts)
+        Ok(format!("{async_}({args}) => {{{stmts}}}"))
     }
 }
 
-pub fn generate_args(ctx: &mut context::Context, args: &Vec<ast::FnArg>) -> String {
-    let result = "";
+/// The type of the value a call to `ast` gives, a `Promise` of its output
+/// when it is async.
+pub fn output_type(ast: &ast::Fn) -> InferedType {
+    let output = InferedType::from(ast.output.clone());
 
+    if ast.is_async && output.id != "Promise" {
+        InferedType {
+            id: "Promise".into(),
+            generics: vec![output.id],
+        }
+    } else {
+        output
+    }
+}
+
+pub fn generate_args(ctx: &mut context::Context, args: &[ast::FnArg]) -> String {
     for arg in args.iter() {
         ctx.add_resolved_type(&arg.id.string, &arg.input.clone().into());
     }
@@ -37,26 +64,33 @@ pub fn generate_args(ctx: &mut context::Context, args: &Vec<ast::FnArg>) -> Stri
         .join(", ")
 }
 
-pub fn generate_body(ctx: &mut context::Context, body: &Vec<ast::FnStmt>) -> String {
+pub fn generate_body(ctx: &mut context::Context, body: &[ast::FnStmt]) -> GenResult<String> {
     let body = body
         .iter()
-        .map(|fn_stmt| match fn_stmt {
-            ast::FnStmt::Let(v) => statement_let::generate(ctx, v),
-            ast::FnStmt::FnCall(v) => fn_call::generate(ctx, v),
-            ast::FnStmt::MemberAccess(v) => member_access::generate(ctx, v),
-            ast::FnStmt::LitJs(v) => literal_js::generate(ctx, v),
-            ast::FnStmt::If(v) => statement_if::generate(ctx, v),
-            ast::FnStmt::Ret(v) => statement_return::generate(ctx, v),
-            ast::FnStmt::Noop => "".to_string(),
+        .map(|fn_stmt| {
+            let code = match fn_stmt {
+                ast::FnStmt::Let(v) => statement_let::generate(ctx, v),
+                ast::FnStmt::FnCall(v) => fn_call::generate(ctx, v),
+                ast::FnStmt::MemberAccess(v) => member_access::generate(ctx, v),
+                ast::FnStmt::LitJs(v) => Ok(literal_js::generate(ctx, v)),
+                ast::FnStmt::If(v) => statement_if::generate_statement(ctx, v),
+                ast::FnStmt::Match(v) => statement_match::generate_statement(ctx, v),
+                ast::FnStmt::Ret(v) => statement_return::generate(ctx, v),
+                ast::FnStmt::Expr(v) => expression::generate(ctx, v),
+                ast::FnStmt::Noop => Ok("".to_string()),
+                ast::FnStmt::Error(span) => {
+                    ctx.throw_custom(span, "Cannot generate a statement that failed to parse.")
+                }
+            }?;
+
+            Ok(source_map::mark(fn_stmt, code))
         })
-        .collect::<Vec<String>>()
+        .collect::<GenResult<Vec<String>>>()?
         .join("\n")
-        .split("\n")
+        .split('\n')
         .map(|x| format!("  {x}"))
         .collect::<Vec<String>>()
         .join("\n");
 
-    let body = format!("\n{body}\n");
-
-    body
+    Ok(format!("\n{body}\n"))
 }
@@ -1,4 +1,145 @@
-use super::context;
-pub(crate) fn generate(ctx: &mut context::Context, v: &crate::ast::If) -> String {
-    "statement_if_todo".into()
+use crate::{
+    ast,
+    span::{Span, Spanned},
+};
+
+use super::context::{self, GenResult};
+use super::{expression, statement_fn};
+
+/// Generates an `if` whose value is used. It becomes a ternary when every
+/// branch is a single expression, and an arrow function called right away
+/// otherwise.
+pub fn generate(ctx: &mut context::Context, ast: &ast::If) -> GenResult<String> {
+    let falsy = match &ast.falsy {
+        Some(falsy) => falsy,
+        None => return ctx.throw_custom(ast, "An if used as a value needs an else branch."),
+    };
+
+    if let (Some(truthy), Some(falsy)) = (single_value(&ast.truthy), single_value(falsy)) {
+        return Ok(format!(
+            "({} ? {} : {})",
+            expression::generate(ctx, &ast.test)?,
+            expression::generate(&mut ctx.clone(), &truthy)?,
+            expression::generate(&mut ctx.clone(), &falsy)?
+        ));
+    }
+
+    let returning = returning(ctx, ast)?;
+    let body = statement_fn::generate_body(ctx, &[ast::FnStmt::If(returning)])?;
+
+    Ok(call_right_away(ast.awaits, "()", &body, ""))
+}
+
+/// An arrow function taking `args` called right away with `values`. When
+/// `awaits`, the function is async and its result awaited.
+pub fn call_right_away(awaits: bool, args: &str, body: &str, values: &str) -> String {
+    if awaits {
+        format!("(await (async {args} => {{{body}}})({values}))")
+    } else {
+        format!("({args} => {{{body}}})({values})")
+    }
+}
+
+/// Generates an `if` used as a statement. Every branch gets its own scope.
+pub fn generate_statement(ctx: &mut context::Context, ast: &ast::If) -> GenResult<String> {
+    let test = expression::generate(ctx, &ast.test)?;
+    let truthy = statement_fn::generate_body(&mut ctx.clone(), &ast.truthy.stmts)?;
+    let code = format!("if ({test}) {{{truthy}}}");
+
+    match &ast.falsy {
+        None => Ok(code),
+        Some(falsy) => match falsy.stmts.as_slice() {
+            [ast::FnStmt::If(else_if)] => {
+                Ok(format!("{code} else {}", generate_statement(ctx, else_if)?))
+            }
+            stmts => Ok(format!(
+                "{code} else {{{}}}",
+                statement_fn::generate_body(&mut ctx.clone(), stmts)?
+            )),
+        },
+    }
+}
+
+/// The expression of a block made of that expression alone.
+fn single_value(block: &ast::Block) -> Option<ast::Expr> {
+    match statements(block).as_slice() {
+        [stmt] => value(stmt),
+        _ => None,
+    }
+}
+
+fn value(stmt: &ast::FnStmt) -> Option<ast::Expr> {
+    match stmt {
+        ast::FnStmt::FnCall(v) => Some(ast::Expr::FnCall(v.clone())),
+        ast::FnStmt::MemberAccess(v) => Some(ast::Expr::MemberAccess(Box::new(v.clone()))),
+        ast::FnStmt::LitJs(v) => Some(ast::Expr::LitJs(v.clone())),
+        ast::FnStmt::If(v) if v.falsy.is_some() => Some(ast::Expr::If(Box::new(v.clone()))),
+        ast::FnStmt::Match(v) => Some(ast::Expr::Match(Box::new(v.clone()))),
+        ast::FnStmt::Expr(v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+fn statements(block: &ast::Block) -> Vec<&ast::FnStmt> {
+    block
+        .stmts
+        .iter()
+        .filter(|stmt| !matches!(stmt, ast::FnStmt::Noop))
+        .collect()
+}
+
+/// Turns the last statement of every branch into a `return` of its value.
+fn returning(ctx: &mut context::Context, ast: &ast::If) -> GenResult<ast::If> {
+    let falsy = match &ast.falsy {
+        Some(falsy) => falsy,
+        None => return ctx.throw_custom(ast, "An if used as a value needs an else branch."),
+    };
+
+    Ok(ast::If {
+        test: ast.test.clone(),
+        truthy: returning_block(ctx, &ast.truthy, "if")?,
+        falsy: Some(returning_block(ctx, falsy, "if")?),
+        awaits: ast.awaits,
+        span: ast.span.clone(),
+    })
+}
+
+/// Turns the last statement of `block` into a `return` of its value, `kind`
+/// names what the block is a branch of in errors.
+pub fn returning_block(
+    ctx: &mut context::Context,
+    block: &ast::Block,
+    kind: &str,
+) -> GenResult<ast::Block> {
+    let mut stmts = statements(block).into_iter().cloned().collect::<Vec<_>>();
+
+    let last =
+        match stmts.pop() {
+            Some(ast::FnStmt::If(v)) => ast::FnStmt::If(returning(ctx, &v)?),
+            Some(last) => match value(&last) {
+                Some(expr) => ast::FnStmt::Ret(ast::Return {
+                    expr,
+                    span: last.span(),
+                }),
+                None => return ctx.throw_custom(
+                    &last,
+                    &format!("This branch has to end with an expression, its value is the value of the {kind}."),
+                ),
+            },
+            None => {
+                let span = Span::new(block.span.to.saturating_sub(1), block.span.to);
+
+                return ctx.throw_custom(
+                &span,
+                &format!("This branch is empty, it has to end with an expression to give the {kind} a value."),
+            );
+            }
+        };
+
+    stmts.push(last);
+
+    Ok(ast::Block {
+        stmts,
+        span: block.span.clone(),
+    })
 }
@@ -0,0 +1,87 @@
+use crate::ast;
+
+use super::{
+    context::{self, GenResult},
+    source_map,
+};
+
+pub fn generate(ast: &ast::Import) -> String {
+    let mut parts = vec![];
+
+    if let Some(default) = &ast.default {
+        parts.push(default.id.string.clone());
+    }
+
+    if let Some(namespace) = &ast.namespace {
+        parts.push(format!("* as {}", namespace.id.string));
+    }
+
+    if !ast.names.is_empty() {
+        let names = ast
+            .names
+            .iter()
+            .map(|name| match &name.exported {
+                Some(exported) => format!("{} as {}", exported.string, name.id.string),
+                None => name.id.string.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        parts.push(format!("{{ {} }}", names.join(", ")));
+    }
+
+    let source = source_map::escape(&ast.source);
+
+    if parts.is_empty() {
+        format!("import \"{source}\"")
+    } else {
+        format!("import {} from \"{source}\"", parts.join(", "))
+    }
+}
+
+/// Registers the imported names as extern symbols. A name declared as a
+/// `Fn<..., Output>` is an extern fn, calls to it resolve the type of their
+/// output, other names hold a value of the type they are declared with.
+pub fn register(ctx: &mut context::Context, ast: &ast::Import) -> GenResult<()> {
+    for name in ast.bindings() {
+        ctx.add_import(name, ast)?;
+
+        let ast::Type::Ident(ty) = &name.ty else {
+            continue;
+        };
+
+        match ty.generics.split_last() {
+            Some((output, inputs)) if ty.string == "Fn" => {
+                let ast_fn = ast::Fn {
+                    id: name.id.clone(),
+                    anonymous: false,
+                    external: true,
+                    public: false,
+                    is_async: false,
+                    args: inputs
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, input)| ast::FnArg {
+                            id: ast::Ident {
+                                string: format!("_{idx}"),
+                                generics: vec![],
+                                span: input.span.clone(),
+                            },
+                            input: ast::Type::Ident(input.clone()),
+                            span: input.span.clone(),
+                        })
+                        .collect(),
+                    stmts: vec![],
+                    output: ast::Type::Ident(output.clone()),
+                    span: name.span.clone(),
+                };
+
+                ctx.add_extern(&name.id.string, &ast_fn, name.id.string.clone())?;
+            }
+            _ => {
+                ctx.add_resolved_type(&name.id.string, &name.ty.clone().into());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -2,11 +2,22 @@ use crate::ast;
 
 use super::expression;
 
-use super::context;
-pub fn generate(ctx: &mut context::Context, ast: &ast::Let) -> String {
-    let name = &ast.id;
-    let expr = expression::generate(ctx, &ast.expr);
-    ctx.add_var(name, &ast);
+use super::context::{self, GenResult};
+pub fn generate(ctx: &mut context::Context, ast: &ast::Let) -> GenResult<String> {
+    generate_named(ctx, ast, &ast.id)
+}
+
+/// Generates `ast` as a variable called `name`, `M_x` for the let `x` of the
+/// mod `M`.
+pub fn generate_named(
+    ctx: &mut context::Context,
+    ast: &ast::Let,
+    name: &String,
+) -> GenResult<String> {
+    let expr = expression::generate(ctx, &ast.expr)?;
+    ctx.add_var(name, ast)?;
+
+    let export = if ast.public { "export " } else { "" };
 
-    format!("let {name} = {expr}")
+    Ok(format!("{export}let {name} = {expr}"))
 }
@@ -0,0 +1,107 @@
+use crate::ast;
+
+use super::context::{self, GenResult};
+use super::{expression, statement_fn, statement_if};
+
+/// The name the matched value is bound to while the arms are tested.
+const SUBJECT: &str = "$match";
+
+/// Generates a `match` whose value is used, an arrow function called right
+/// away with the matched value.
+pub fn generate(ctx: &mut context::Context, ast: &ast::Match) -> GenResult<String> {
+    let expr = expression::generate(ctx, &ast.expr)?;
+    let arms = ast
+        .arms
+        .iter()
+        .map(|arm| {
+            Ok(ast::MatchArm {
+                body: statement_if::returning_block(ctx, &arm.body, "match")?,
+                ..arm.clone()
+            })
+        })
+        .collect::<GenResult<Vec<_>>>()?;
+    let chain = generate_chain(ctx, &arms)?;
+
+    Ok(statement_if::call_right_away(
+        ast.awaits,
+        &format!("({SUBJECT})"),
+        &indent(&chain),
+        &expr,
+    ))
+}
+
+/// Generates a `match` used as a statement, the arms become an if-chain
+/// testing the tag of the value.
+pub fn generate_statement(ctx: &mut context::Context, ast: &ast::Match) -> GenResult<String> {
+    let expr = expression::generate(ctx, &ast.expr)?;
+    let chain = generate_chain(ctx, &ast.arms)?;
+
+    Ok(format!(
+        "{{{}}}",
+        indent(&format!("let {SUBJECT} = {expr}\n{chain}"))
+    ))
+}
+
+fn generate_chain(ctx: &mut context::Context, arms: &[ast::MatchArm]) -> GenResult<String> {
+    let mut branches = vec![];
+
+    for arm in arms {
+        let mut conditions = vec![];
+        let mut bindings = vec![];
+
+        destructure(ctx, &arm.pattern, SUBJECT, &mut conditions, &mut bindings)?;
+
+        let body = statement_fn::generate_body(&mut ctx.clone(), &arm.body.stmts)?;
+        let bindings = bindings
+            .iter()
+            .map(|binding| format!("\n  {binding}"))
+            .collect::<String>();
+        let block = format!("{{{bindings}{body}}}");
+
+        if conditions.is_empty() {
+            branches.push(block);
+            break;
+        }
+
+        branches.push(format!("if ({}) {block}", conditions.join(" && ")));
+    }
+
+    Ok(branches.join(" else "))
+}
+
+/// Collects the tests `pattern` makes on the value at `path` and the names it
+/// binds.
+fn destructure(
+    ctx: &mut context::Context,
+    pattern: &ast::Pattern,
+    path: &str,
+    conditions: &mut Vec<String>,
+    bindings: &mut Vec<String>,
+) -> GenResult<()> {
+    match pattern {
+        ast::Pattern::Variant(v) => {
+            conditions.push(format!("{path}.$tag === \"{}\"", v.id.string));
+
+            for (idx, arg) in v.args.iter().enumerate() {
+                destructure(ctx, arg, &format!("{path}[{idx}]"), conditions, bindings)?;
+            }
+        }
+        ast::Pattern::Literal(v) => {
+            conditions.push(format!("{path} === {}", expression::generate(ctx, v)?));
+        }
+        ast::Pattern::Wildcard(_) => {}
+        ast::Pattern::Binding(v) => bindings.push(format!("let {} = {path}", v.string)),
+    }
+
+    Ok(())
+}
+
+fn indent(code: &str) -> String {
+    let lines = code
+        .split('\n')
+        .map(|line| format!("  {line}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("\n{lines}\n")
+}
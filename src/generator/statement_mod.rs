@@ -1,30 +1,60 @@
 use crate::ast;
 
-use super::context;
-use super::statement_fn;
+use super::context::{self, GenResult};
+use super::{source_map, statement_fn, statement_let};
 
-pub(crate) fn generate(ctx: &mut context::Context, module: &crate::ast::Mod) -> String {
-    let path = module.path.replace(".", "_");
+pub(crate) fn generate(ctx: &mut context::Context, module: &crate::ast::Mod) -> GenResult<String> {
+    let path = module.path.replace('.', "_");
+
+    // The fns and lets of the mod can refer to each other through it.
+    ctx.add_mod(&module.path, module)?;
 
     let mod_ctx = &mut ctx.with_module_path(path.clone());
 
     let result = module
         .stmts
         .iter()
-        .filter(|stmt| match stmt {
-            ast::Stmt::Noop => false,
-            _ => true,
-        })
-        .map(|stmt| match stmt {
-            ast::Stmt::Fn(v) => statement_fn::generate(mod_ctx, v),
-            ast::Stmt::Noop => "".into(),
-            _ => ctx.throw_custom(stmt, "Unsupported statement while generating module"),
+        .filter(|stmt| !matches!(stmt, ast::Stmt::Noop))
+        .map(|stmt| {
+            let code = match stmt {
+                ast::Stmt::Fn(v) => statement_fn::generate(mod_ctx, v),
+                ast::Stmt::Let(v) => {
+                    statement_let::generate_named(mod_ctx, v, &format!("{path}_{}", v.id))
+                }
+                ast::Stmt::Noop => Ok("".into()),
+                _ => ctx.throw_custom(stmt, "Unsupported statement while generating module"),
+            }?;
+
+            Ok(source_map::mark(stmt, code))
         })
-        .collect::<Vec<_>>()
+        .collect::<GenResult<Vec<_>>>()?
         .join("\n");
 
     ctx.merge(mod_ctx);
-    ctx.add_mod(&module.path, &module);
 
-    result
+    for stmt in &module.stmts {
+        if let ast::Stmt::Let(v) = stmt {
+            ctx.add_var(&format!("{path}_{}", v.id), v)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Registers the fns of an extern mod under the names the fns of a mod are
+/// generated with, calls to them are generated as calls to `Deno.readTextFile`.
+pub(crate) fn register_extern(ctx: &mut context::Context, module: &ast::Mod) -> GenResult<()> {
+    let path = module.path.replace('.', "_");
+
+    for stmt in &module.stmts {
+        if let ast::Stmt::Fn(v) = stmt {
+            let js_name = format!("{}.{}", module.path, v.id.string);
+
+            ctx.add_extern(&format!("{path}_{}", v.id.string), v, js_name)?;
+        }
+    }
+
+    ctx.add_mod(&module.path, module)?;
+
+    Ok(())
 }
@@ -1,7 +1,7 @@
 use crate::{ast, generator::expression};
 
-use super::context;
+use super::context::{self, GenResult};
 
-pub fn generate(ctx: &mut context::Context, ast: &ast::Return) -> String {
-    format!("return {}", expression::generate(ctx, &ast.expr))
+pub fn generate(ctx: &mut context::Context, ast: &ast::Return) -> GenResult<String> {
+    Ok(format!("return {}", expression::generate(ctx, &ast.expr)?))
 }
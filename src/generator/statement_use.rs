@@ -0,0 +1,226 @@
+use std::path::Path;
+
+use crate::{ast, loader::UsedFile, span::Span, utils};
+
+use super::{
+    context::{self, GenResult},
+    source_map, statement_enum,
+};
+
+/// Registers the mods, fns, records and enums of the used file so calls to them resolve, and
+/// imports the pub ones from the file generated next to this one.
+pub fn generate(ctx: &mut context::Context, ast: &ast::Use) -> GenResult<String> {
+    let (file_path, specifier) = resolve(&ctx.file_path, ast);
+
+    let root_ast = match ctx.used_files.clone().get(&file_path) {
+        Some(UsedFile::Parsed(root_ast)) => root_ast.clone(),
+        Some(UsedFile::Unreadable(err)) => {
+            return ctx.throw_custom(ast, &format!("Cannot read {file_path}: {err}."))
+        }
+        Some(UsedFile::Invalid {
+            content: file_content,
+            diagnostics,
+        }) => {
+            let error = ctx.error(ast, &format!("The file {file_path} used here has errors."));
+
+            return Err(diagnostics.iter().fold(error, |error, diagnostic| {
+                let (line, column) = utils::line_col(file_content, diagnostic.span.from);
+                let message = format!(
+                    "{file_path}:{}:{}: {}",
+                    line + 1,
+                    column + 1,
+                    diagnostic.message
+                );
+
+                error.with_note(&message, None)
+            }));
+        }
+        None => return ctx.throw_custom(ast, &format!("Cannot read {file_path}.")),
+    };
+
+    let specifier = source_map::escape(&specifier);
+    let names = register(ctx, &ast.span, &root_ast)?
+        .into_iter()
+        .flat_map(|(_, names)| names)
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        Ok(format!("import \"{specifier}\""))
+    } else {
+        Ok(format!(
+            "import {{ {} }} from \"{specifier}\"",
+            names.join(", ")
+        ))
+    }
+}
+
+/// The path of the file `ast` uses, relative to `file_path`, and the specifier
+/// it is imported with.
+pub fn resolve(file_path: &str, ast: &ast::Use) -> (String, String) {
+    let (source, specifier) = match &ast.path {
+        ast::UsePath::Module(paths) => {
+            let path = paths.join("/");
+            (format!("{path}.zac"), format!("./{path}.jsx"))
+        }
+        ast::UsePath::File(path) => {
+            let specifier = format!("{}.jsx", path.strip_suffix(".zac").unwrap_or(path));
+
+            if specifier.starts_with('.') || specifier.starts_with('/') {
+                (path.clone(), specifier)
+            } else {
+                (path.clone(), format!("./{specifier}"))
+            }
+        }
+    };
+
+    let dir = Path::new(file_path).parent().unwrap_or(Path::new(""));
+    let source = utils::normalize_path(&dir.join(source))
+        .to_string_lossy()
+        .to_string();
+
+    (source, specifier)
+}
+
+/// Adds the pub fns, lets and enums, the mods, records and externs of another
+/// file to `ctx`, fns under the names they are generated with. The
+/// definitions the file keeps to itself are left out, the checker reports
+/// their uses. The definitions live in the
+/// other file, errors about them point at `span` instead. Every definition is
+/// returned by the name it is registered with, along with the names of the
+/// fns and enum constructors it is imported with.
+pub fn register(
+    ctx: &mut context::Context,
+    span: &Span,
+    root_ast: &ast::Root,
+) -> GenResult<Vec<(String, Vec<String>)>> {
+    let mut definitions = vec![];
+
+    for stmt in &root_ast.stmts {
+        match stmt {
+            ast::Stmt::Fn(v) if v.public || v.external => {
+                let id = register_fn(ctx, span, "", v)?;
+
+                definitions.push((id.clone(), import_names(&id, v)));
+            }
+            ast::Stmt::Mod(v) => {
+                let fns = v.stmts.iter().filter_map(|stmt| match stmt {
+                    ast::Stmt::Fn(v) if v.public || v.external => Some(v),
+                    _ => None,
+                });
+
+                for v_fn in fns {
+                    let id = register_fn(ctx, span, &v.path, v_fn)?;
+
+                    definitions.push((id.clone(), import_names(&id, v_fn)));
+                }
+
+                let lets = v.stmts.iter().filter_map(|stmt| match stmt {
+                    ast::Stmt::Let(v) if v.public => Some(v),
+                    _ => None,
+                });
+
+                for v_let in lets {
+                    let id = format!("{}_{}", v.path.replace('.', "_"), v_let.id);
+
+                    register_let(ctx, span, &id, v_let)?;
+                    definitions.push((id.clone(), vec![id]));
+                }
+
+                let ast_mod = ast::Mod {
+                    span: span.clone(),
+                    ..v.clone()
+                };
+
+                ctx.add_mod(&v.path, &ast_mod)?;
+                definitions.push((v.path.clone(), vec![]));
+            }
+            ast::Stmt::Let(v) if v.public => {
+                register_let(ctx, span, &v.id, v)?;
+                definitions.push((v.id.clone(), vec![v.id.clone()]));
+            }
+            ast::Stmt::Record(v) => {
+                let ast_record = ast::Record {
+                    span: span.clone(),
+                    ..v.clone()
+                };
+
+                ctx.add_record(&v.id.string, &ast_record)?;
+                definitions.push((v.id.string.clone(), vec![]));
+            }
+            ast::Stmt::Enum(v) if v.public => {
+                let ast_enum = ast::Enum {
+                    variants: v
+                        .variants
+                        .iter()
+                        .map(|variant| ast::EnumVariant {
+                            span: span.clone(),
+                            ..variant.clone()
+                        })
+                        .collect(),
+                    span: span.clone(),
+                    ..v.clone()
+                };
+
+                ctx.add_enum(&v.id.string, &ast_enum)?;
+                definitions.push((v.id.string.clone(), statement_enum::constructor_names(v)));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(definitions)
+}
+
+fn register_let(
+    ctx: &mut context::Context,
+    span: &Span,
+    id: &String,
+    ast_let: &ast::Let,
+) -> GenResult<()> {
+    let ast_let = ast::Let {
+        span: span.clone(),
+        ..ast_let.clone()
+    };
+
+    ctx.add_var(id, &ast_let)?;
+
+    Ok(())
+}
+
+fn register_fn(
+    ctx: &mut context::Context,
+    span: &Span,
+    module_path: &str,
+    ast_fn: &ast::Fn,
+) -> GenResult<String> {
+    let (id, js_name) = if !module_path.is_empty() {
+        (
+            format!("{}_{}", module_path.replace('.', "_"), ast_fn.id.string),
+            format!("{}.{}", module_path, ast_fn.id.string),
+        )
+    } else {
+        (ast_fn.id.string.clone(), ast_fn.id.string.clone())
+    };
+
+    let ast_fn = ast::Fn {
+        span: span.clone(),
+        ..ast_fn.clone()
+    };
+
+    if ast_fn.external {
+        ctx.add_extern(&id, &ast_fn, js_name)?;
+    } else {
+        ctx.add_fn(&id, &ast_fn)?;
+    }
+
+    Ok(id)
+}
+
+/// Extern fns are not generated, there is nothing to import for them.
+fn import_names(id: &str, ast_fn: &ast::Fn) -> Vec<String> {
+    if ast_fn.external {
+        vec![]
+    } else {
+        vec![id.to_string()]
+    }
+}
@@ -0,0 +1,15 @@
+use crate::ast;
+
+use super::context::{self, GenResult};
+use super::{binary, expression};
+
+pub fn generate(ctx: &mut context::Context, v: &ast::Unary) -> GenResult<String> {
+    let op = v.op.value();
+    let expr = match &v.expr {
+        // `- -a` would read as a decrement without the parentheses.
+        ast::Expr::Unary(_) => format!("({})", expression::generate(ctx, &v.expr)?),
+        expr => binary::operand(ctx, expr, ast::UNARY_PRECEDENCE)?,
+    };
+
+    Ok(format!("{op}{expr}"))
+}
@@ -0,0 +1,37 @@
+use crate::ast;
+
+use super::{
+    context::{self, GenResult},
+    fn_call, statement_enum,
+};
+
+/// Generates `.Some(x)`, the checker makes sure a single enum has the variant.
+pub fn generate(ctx: &mut context::Context, ast: &ast::Variant) -> GenResult<String> {
+    let name = &ast.id.string;
+    let enum_def = ctx
+        .enum_defs
+        .values()
+        .find(|enum_def| enum_def.variants.iter().any(|v| &v.id.string == name))
+        .cloned();
+
+    match enum_def {
+        Some(enum_def) => generate_qualified(ctx, &enum_def, &ast.id, &ast.args),
+        None => ctx.throw_custom(&ast.id, &format!("No enum has the variant {name}.")),
+    }
+}
+
+/// Generates the variant `id` of `enum_def` called with `args`, `Option.Some(x)`.
+pub fn generate_qualified(
+    ctx: &mut context::Context,
+    enum_def: &ast::Enum,
+    id: &ast::Ident,
+    args: &[ast::Expr],
+) -> GenResult<String> {
+    let name = statement_enum::constructor_name(&enum_def.id.string, &id.string);
+
+    if args.is_empty() {
+        Ok(name)
+    } else {
+        Ok(format!("{name}({})", fn_call::generate_args(ctx, args)?))
+    }
+}
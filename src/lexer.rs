@@ -1,12 +1,15 @@
-use crate::{error_message::ErrorMessage, span::Span, token::Token};
+use crate::{
+    diagnostic::{code, Diagnostic},
+    span::Span,
+    token::{Comment, Token},
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 
 lazy_static! {
     static ref COMMENT_LINE: Regex = Regex::new(r"^(//.+)").unwrap();
     static ref COMMENT_BLOCK: Regex = Regex::new(r"^(/\*(?s)(.*?)\*/)").unwrap();
-    static ref STRING_LITERAL: Regex = Regex::new(r#"^\s*"([^"\\]*(?s:\\.[^"\\]*)*)""#).unwrap();
-    static ref NUMBER_LITERAL: Regex = Regex::new(r#"^([+-]?([0-9]*[.])?[0-9]+)"#).unwrap();
+    static ref NUMBER_LITERAL: Regex = Regex::new(r#"^(([0-9]*[.])?[0-9]+)"#).unwrap();
     static ref BOOLEAN_LITERAL: Regex = Regex::new(r#"^((true)|(false))"#).unwrap();
     static ref JS_LITERAL: Regex = Regex::new(r"^`(?s)(.*?)`").unwrap();
     static ref JSX_A: Regex = Regex::new(r"^<([A-Za-z0-9]+)(?s:.*?)>").unwrap();
@@ -18,6 +21,8 @@ lazy_static! {
     static ref DOT: Regex = Regex::new(r"^\.").unwrap();
     static ref COLON: Regex = Regex::new(r"^:").unwrap();
     static ref COMMA: Regex = Regex::new(r"^,").unwrap();
+    static ref LE: Regex = Regex::new(r"^<=").unwrap();
+    static ref GE: Regex = Regex::new(r"^>=").unwrap();
     static ref LT: Regex = Regex::new(r"^<").unwrap();
     static ref GT: Regex = Regex::new(r"^>").unwrap();
     static ref LPAREN: Regex = Regex::new(r"^\(").unwrap();
@@ -26,12 +31,17 @@ lazy_static! {
     static ref RBRACE: Regex = Regex::new(r"^\}").unwrap();
     static ref LBRCKT: Regex = Regex::new(r"^\[").unwrap();
     static ref RBRCKT: Regex = Regex::new(r"^\]").unwrap();
-    static ref ADD: Regex = Regex::new(r"^+").unwrap();
+    static ref ADD: Regex = Regex::new(r"^\+").unwrap();
     static ref SUB: Regex = Regex::new(r"^-").unwrap();
     static ref DIV: Regex = Regex::new(r"^/").unwrap();
-    static ref MUL: Regex = Regex::new(r"^/").unwrap();
+    static ref MUL: Regex = Regex::new(r"^\*").unwrap();
+    static ref AND: Regex = Regex::new(r"^&&").unwrap();
+    static ref OR: Regex = Regex::new(r"^\|\|").unwrap();
+    static ref PIPE: Regex = Regex::new(r"^\|>").unwrap();
     static ref NE: Regex = Regex::new(r"^!=").unwrap();
+    static ref NOT: Regex = Regex::new(r"^!").unwrap();
     static ref EQ: Regex = Regex::new(r"^==").unwrap();
+    static ref ARROW: Regex = Regex::new(r"^=>").unwrap();
     static ref ASSIGNMENT: Regex = Regex::new(r"^=").unwrap();
     static ref NEWLINE: Regex = Regex::new(r"^[\n]+").unwrap();
 }
@@ -43,6 +53,8 @@ pub struct Lexer {
     pub len: usize,
     pub pos: usize,
     pub lines: usize,
+    pub comments: Vec<Comment>,
+    peeked_comments: Vec<Comment>,
 }
 
 impl Lexer {
@@ -55,19 +67,29 @@ impl Lexer {
             len,
             pos: 0,
             lines: 0,
+            comments: vec![],
+            peeked_comments: vec![],
         }
     }
 
-    pub fn get_next_token(&mut self, do_not_consume_jsx: bool, inside_jsx: bool) -> Token {
-        let result = self.get_next_token_ex(do_not_consume_jsx, inside_jsx);
-        // println!("{:?}", result);
-        return result;
+    /// A lexer over `from..to` of the content, its spans still point into the
+    /// whole file.
+    pub fn slice(&self, from: usize, to: usize) -> Self {
+        Lexer {
+            pos: from,
+            ..Lexer::new(self.filepath.clone(), self.content[..to].to_string())
+        }
     }
 
-    pub fn get_next_token_ex(&mut self, do_not_consume_jsx: bool, inside_jsx: bool) -> Token {
-        let (next_pos, token) = self.peek_ahead(self.pos, do_not_consume_jsx, inside_jsx);
+    pub fn get_next_token(
+        &mut self,
+        do_not_consume_jsx: bool,
+        inside_jsx: bool,
+    ) -> Result<Token, Diagnostic> {
+        let (next_pos, token) = self.peek_ahead(self.pos, do_not_consume_jsx, inside_jsx)?;
         self.pos = next_pos;
-        token
+        self.comments.append(&mut self.peeked_comments);
+        Ok(token)
     }
 
     pub fn peek_ahead(
@@ -75,12 +97,14 @@ impl Lexer {
         starting_pos: usize,
         do_not_consume_jsx: bool,
         inside_jsx: bool,
-    ) -> (usize, Token) {
+    ) -> Result<(usize, Token), Diagnostic> {
         let mut current_pos = starting_pos;
 
+        self.peeked_comments.clear();
+
         loop {
             if current_pos == self.len {
-                return (self.len, Token::Eof(Span::new(self.len, self.len)));
+                return Ok((self.len, Token::Eof(Span::new(self.len, self.len))));
             }
 
             let slice = &self.content.clone()[current_pos..];
@@ -88,10 +112,12 @@ impl Lexer {
             match slice {
                 _ if COMMENT_LINE.is_match(slice) => {
                     let cap = &COMMENT_LINE.captures(slice).unwrap()[1];
+                    self.peek_comment(current_pos, cap);
                     current_pos = self.peek_ahead_advance(current_pos, cap.len());
                 }
                 _ if COMMENT_BLOCK.is_match(slice) => {
                     let cap = &COMMENT_BLOCK.captures(slice).unwrap()[1];
+                    self.peek_comment(current_pos, cap);
                     current_pos = self.peek_ahead_advance(current_pos, cap.len());
                 }
                 _ if WHITESPACE.is_match(slice) => {
@@ -106,17 +132,17 @@ impl Lexer {
                         cap_inner.to_string(),
                         self.span(current_pos, cap_overall.len()),
                     );
-                    return self.peek_ahead_advance_with_token(
+                    return Ok(self.peek_ahead_advance_with_token(
                         current_pos,
                         cap_overall.len(),
                         token,
-                    );
+                    ));
                 }
                 _ if JSX_SELF_CLOSE.is_match(slice) => {
                     let caps = JSX_SELF_CLOSE.captures(slice).unwrap();
                     let cap = &caps[0];
                     let token = Token::JsxSelfClose(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if JSX_CLOSE.is_match(slice) => {
                     let caps = JSX_CLOSE.captures(slice).unwrap();
@@ -124,34 +150,48 @@ impl Lexer {
                     let name = &caps[1];
                     let token =
                         Token::JsxClose(name.to_string(), self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if inside_jsx && JSX_STRING_LITERAL.is_match(slice) => {
                     let caps = JSX_STRING_LITERAL.captures(slice).unwrap();
                     let cap = &caps[0];
                     let token = Token::Str(cap.to_string(), self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
-                _ if !inside_jsx && STRING_LITERAL.is_match(slice) => {
-                    let caps = STRING_LITERAL.captures(slice).unwrap();
-                    let cap1 = &caps[0];
-                    let cap2 = &caps[1];
-                    let token = Token::Str(cap2.to_string(), self.span(current_pos, cap1.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap1.len(), token);
+                _ if !inside_jsx && string_literal_len(slice.trim_start()).is_some() => {
+                    let literal = slice.trim_start();
+                    let len = string_literal_len(literal).unwrap();
+                    let overall = slice.len() - literal.len() + len;
+                    let token = Token::Str(
+                        literal[1..len - 1].to_string(),
+                        self.span(current_pos, overall),
+                    );
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, overall, token));
+                }
+                _ if !inside_jsx && slice.trim_start().starts_with('"') => {
+                    let from = current_pos + slice.len() - slice.trim_start().len();
+                    let line_len = self.content[from..].find('\n').unwrap_or(self.len - from);
+
+                    return Err(Diagnostic::error(
+                        self.filepath.clone(),
+                        "This string is never closed, or one of its interpolations is missing its `}`.".to_string(),
+                        Span::new(from, from + line_len),
+                    )
+                    .with_code(code::SYNTAX_ERROR));
                 }
                 _ if NUMBER_LITERAL.is_match(slice) => {
                     let caps = NUMBER_LITERAL.captures(slice).unwrap();
                     let cap1 = &caps[0];
                     let token =
                         Token::Numeric(cap1.to_string(), self.span(current_pos, cap1.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap1.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap1.len(), token));
                 }
                 _ if BOOLEAN_LITERAL.is_match(slice) => {
                     let caps = BOOLEAN_LITERAL.captures(slice).unwrap();
                     let cap1 = &caps[0];
                     let token =
                         Token::Boolean(cap1.to_string(), self.span(current_pos, cap1.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap1.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap1.len(), token));
                 }
                 _ if IDENTIFIER.is_match(slice) => {
                     let cap = &IDENTIFIER.captures(slice).unwrap()[0];
@@ -161,34 +201,46 @@ impl Lexer {
                         "return" => Token::Return(span),
                         "mod" => Token::Mod(span),
                         "let" => Token::Let(span),
+                        "if" => Token::If(span),
+                        "else" => Token::Else(span),
                         _ => Token::Id(cap.to_string(), span),
                     };
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if NEWLINE.is_match(slice) => {
                     let cap = &NEWLINE.captures(slice).unwrap()[0];
                     let token = Token::NewLine(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if DOT.is_match(slice) => {
                     let cap = &DOT.captures(slice).unwrap()[0];
                     let token = Token::Dot(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if COLON.is_match(slice) => {
                     let cap = &COLON.captures(slice).unwrap()[0];
                     let token = Token::DblColon(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if COMMA.is_match(slice) => {
                     let cap = &COMMA.captures(slice).unwrap()[0];
                     let token = Token::Comma(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if GE.is_match(slice) => {
+                    let cap = &GE.captures(slice).unwrap()[0];
+                    let token = Token::GtEq(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if LE.is_match(slice) => {
+                    let cap = &LE.captures(slice).unwrap()[0];
+                    let token = Token::LtEq(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if GT.is_match(slice) => {
                     let cap = &GT.captures(slice).unwrap()[0];
                     let token = Token::Gt(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if LT.is_match(slice) => {
                     if !do_not_consume_jsx && JSX_A.is_match(slice) {
@@ -197,78 +249,143 @@ impl Lexer {
                             caps[1].to_string(),
                             self.span(current_pos, caps[1].len()),
                         );
-                        return self.peek_ahead_advance_with_token(
+                        return Ok(self.peek_ahead_advance_with_token(
                             current_pos,
                             caps[1].len() + 1,
                             token,
-                        );
+                        ));
                     }
 
                     let cap = &LT.captures(slice).unwrap()[0];
                     let token = Token::Lt(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if LPAREN.is_match(slice) => {
                     let cap = &LPAREN.captures(slice).unwrap()[0];
                     let token = Token::LParen(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if RPAREN.is_match(slice) => {
                     let cap = &RPAREN.captures(slice).unwrap()[0];
                     let token = Token::RParen(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if LBRACE.is_match(slice) => {
                     let cap = &LBRACE.captures(slice).unwrap()[0];
                     let token = Token::LBrace(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if RBRACE.is_match(slice) => {
                     let cap = &RBRACE.captures(slice).unwrap()[0];
                     let token = Token::RBrace(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if LBRCKT.is_match(slice) => {
                     let cap = &LBRCKT.captures(slice).unwrap()[0];
                     let token = Token::LSqrBr(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if RBRCKT.is_match(slice) => {
                     let cap = &RBRCKT.captures(slice).unwrap()[0];
                     let token = Token::RSqrBr(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if ADD.is_match(slice) => {
+                    let cap = &ADD.captures(slice).unwrap()[0];
+                    let token = Token::Plus(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if SUB.is_match(slice) => {
+                    let cap = &SUB.captures(slice).unwrap()[0];
+                    let token = Token::Minus(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if MUL.is_match(slice) => {
+                    let cap = &MUL.captures(slice).unwrap()[0];
+                    let token = Token::Multiply(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if DIV.is_match(slice) => {
+                    let cap = &DIV.captures(slice).unwrap()[0];
+                    let token = Token::Divide(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if AND.is_match(slice) => {
+                    let cap = &AND.captures(slice).unwrap()[0];
+                    let token = Token::AndAnd(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if OR.is_match(slice) => {
+                    let cap = &OR.captures(slice).unwrap()[0];
+                    let token = Token::OrOr(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if PIPE.is_match(slice) => {
+                    let cap = &PIPE.captures(slice).unwrap()[0];
+                    let token = Token::Pipe(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if NE.is_match(slice) => {
+                    let cap = &NE.captures(slice).unwrap()[0];
+                    let token = Token::NotEq(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if NOT.is_match(slice) => {
+                    let cap = &NOT.captures(slice).unwrap()[0];
+                    let token = Token::Bang(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if EQ.is_match(slice) => {
+                    let cap = &EQ.captures(slice).unwrap()[0];
+                    let token = Token::EqEq(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
+                }
+                _ if ARROW.is_match(slice) => {
+                    let cap = &ARROW.captures(slice).unwrap()[0];
+                    let token = Token::Arrow(self.span(current_pos, cap.len()));
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ if ASSIGNMENT.is_match(slice) => {
                     let cap = &ASSIGNMENT.captures(slice).unwrap()[0];
                     let token = Token::Eq(self.span(current_pos, cap.len()));
-                    return self.peek_ahead_advance_with_token(current_pos, cap.len(), token);
+                    return Ok(self.peek_ahead_advance_with_token(current_pos, cap.len(), token));
                 }
                 _ => {
-                    let character = &self.content[current_pos..current_pos + 1];
+                    let character = &self.content[current_pos..];
+                    let character = character.chars().next().unwrap_or_default();
 
-                    ErrorMessage::new(
-                        "".into(),
-                        self.content.clone(),
+                    return Err(Diagnostic::error(
+                        self.filepath.clone(),
                         format!(
                             "I'm sorry but we do not support the character '{}'",
                             match character {
-                                "\r" => "\\r",
-                                "\t" => "\\t",
-                                "\n" => "\\n",
-                                " " => "{space}",
-                                _ => character,
+                                '\r' => "\\r".to_string(),
+                                '\t' => "\\t".to_string(),
+                                '\n' => "\\n".to_string(),
+                                ' ' => "{space}".to_string(),
+                                _ => character.to_string(),
                             }
-                            .to_string()
                         ),
-                        current_pos,
+                        Span::new(current_pos, current_pos + character.len_utf8()),
                     )
-                    .print();
-                    panic!()
+                    .with_code(code::UNSUPPORTED_CHARACTER));
                 }
             }
         }
     }
 
+    /// Comments are only kept once the token following them is consumed, so
+    /// peeking ahead does not record them twice.
+    fn peek_comment(&mut self, current_pos: usize, value: &str) {
+        let value = value.trim_end();
+        let span = self.span(current_pos, value.len());
+
+        self.peeked_comments.push(Comment {
+            value: value.to_string(),
+            span,
+        });
+    }
+
     pub fn peek_ahead_advance(&mut self, current_pos: usize, how_many: usize) -> usize {
         current_pos + how_many
     }
@@ -297,6 +414,58 @@ impl Lexer {
     }
 }
 
-pub(crate) fn new(filepath: &String, content: &String) -> Lexer {
-    Lexer::new(filepath.clone(), content.clone())
+/// The length of the string literal `slice` starts with, quotes included. The
+/// expressions interpolated in it can contain strings of their own, as in
+/// `"#{names.join(", ")}"`.
+fn string_literal_len(slice: &str) -> Option<usize> {
+    let bytes = slice.as_bytes();
+
+    if bytes.first() != Some(&b'"') {
+        return None;
+    }
+
+    let mut i = 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            b'#' if bytes.get(i + 1) == Some(&b'{') => {
+                i += 2 + interpolation_len(&slice[i + 2..])?;
+            }
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+/// The length of the interpolated expression `slice` starts with, up to and
+/// including the `}` closing it.
+pub fn interpolation_len(slice: &str) -> Option<usize> {
+    let bytes = slice.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i += string_literal_len(&slice[i..])?,
+            b'{' => {
+                depth += 1;
+                i += 1;
+            }
+            b'}' if depth == 0 => return Some(i + 1),
+            b'}' => {
+                depth -= 1;
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+pub fn new(filepath: &str, content: &str) -> Lexer {
+    Lexer::new(filepath.to_string(), content.to_string())
 }
@@ -0,0 +1,108 @@
+pub mod ast;
+pub mod checker;
+pub mod diagnostic;
+pub mod formatter;
+pub mod generator;
+pub mod lexer;
+pub mod loader;
+pub mod parser;
+pub mod prelude;
+pub mod span;
+pub mod token;
+pub mod utils;
+
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+pub use diagnostic::Diagnostic;
+pub use loader::{FsLoader, Loader};
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The specifier the generated code imports the core library with, the
+    /// `zac_core.js` written next to it by default. `None` leaves the core
+    /// library out.
+    pub prelude: Option<String>,
+    pub target: Target,
+    /// Reads the files `use` statements refer to. None are found by default,
+    /// `FsLoader` reads them from disk.
+    pub loader: Arc<dyn Loader>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            prelude: Some(format!("./{}", prelude::FILE_NAME)),
+            target: Target::Module,
+            loader: Arc::new(HashMap::<String, String>::new()),
+        }
+    }
+}
+
+/// How the generated code gets loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Target {
+    /// An ES module, the top level can use `.await`.
+    #[default]
+    Module,
+    /// A classic script, `.await` only works inside of a fn.
+    Script,
+}
+
+#[derive(Debug, Clone)]
+pub struct Output {
+    pub code: String,
+    pub source_map: generator::SourceMap,
+}
+
+/// Compiles a single Zac source file into JSX, entirely in memory.
+///
+/// `path` labels diagnostics and locates the files `use` statements refer to,
+/// which are read through `options.loader` only, once each. Nothing is written.
+/// Unless `options.prelude` is `None`, the code imports what it uses of the
+/// core library, generated by `prelude::generate`.
+pub fn compile(path: &str, source: &str, options: &Options) -> Result<Output, Vec<Diagnostic>> {
+    let mut lexer = lexer::new(path, source);
+    let ast = parser::parse(&mut lexer)?;
+    let used_files = Rc::new(loader::load_used(path, &ast, options.loader.as_ref()));
+
+    let methods = checker::check(
+        path,
+        &ast,
+        options.prelude.as_ref().map(|_| prelude::root()),
+        options.target,
+        used_files.clone(),
+    )?;
+
+    let prelude = options
+        .prelude
+        .as_ref()
+        .map(|specifier| generator::Prelude {
+            root_ast: prelude::root(),
+            specifier,
+        });
+    let (code, source_map) = generator::generate(
+        path.to_string(),
+        source.to_string(),
+        &ast,
+        prelude,
+        methods,
+        used_files,
+    )
+    .map_err(|err| vec![err])?;
+
+    Ok(Output { code, source_map })
+}
+
+pub fn compile_str(source: &str, options: &Options) -> Result<Output, Vec<Diagnostic>> {
+    compile("<anonymous>", source, options)
+}
+
+/// Formats a single Zac source file in the canonical style, comments included.
+///
+/// Files that do not parse are left alone and their diagnostics returned.
+pub fn format(path: &str, source: &str) -> Result<String, Vec<Diagnostic>> {
+    let mut lexer = lexer::new(path, source);
+    let ast = parser::parse(&mut lexer)?;
+
+    Ok(formatter::format(&ast, source))
+}
@@ -0,0 +1,78 @@
+use std::{collections::HashMap, fmt, fs, io};
+
+use crate::{ast, generator, lexer, parser, Diagnostic};
+
+/// Reads the files `use` statements refer to, by their path.
+pub trait Loader: fmt::Debug {
+    fn load(&self, path: &str) -> io::Result<String>;
+}
+
+/// Reads the used files from disk, like `zac build` does.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsLoader;
+
+impl Loader for FsLoader {
+    fn load(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+}
+
+/// Files kept in memory by their path, the other paths are not found.
+impl Loader for HashMap<String, String> {
+    fn load(&self, path: &str) -> io::Result<String> {
+        self.get(path)
+            .cloned()
+            .ok_or_else(|| io::ErrorKind::NotFound.into())
+    }
+}
+
+/// A file a `use` refers to, loaded and parsed once for both the checker and
+/// the generator.
+#[derive(Debug, Clone)]
+pub enum UsedFile {
+    Parsed(ast::Root),
+    /// The file cannot be read, along with the reason why.
+    Unreadable(String),
+    /// The file does not parse, its content locates the diagnostics.
+    Invalid {
+        content: String,
+        diagnostics: Vec<Diagnostic>,
+    },
+}
+
+/// The used files by their path.
+pub type UsedFiles = HashMap<String, UsedFile>;
+
+/// Loads the files the `use` statements of `root_ast` refer to, `file_path`
+/// being the path of the file they are in. The files they use in turn are not
+/// loaded.
+pub fn load_used(file_path: &str, root_ast: &ast::Root, loader: &dyn Loader) -> UsedFiles {
+    let mut used_files = UsedFiles::new();
+
+    for stmt in &root_ast.stmts {
+        let ast::Stmt::Use(v) = stmt else {
+            continue;
+        };
+
+        let (path, _) = generator::resolve_use(file_path, v);
+
+        if used_files.contains_key(&path) {
+            continue;
+        }
+
+        let used_file = match loader.load(&path) {
+            Ok(content) => match parser::parse(&mut lexer::new(&path, &content)) {
+                Ok(root_ast) => UsedFile::Parsed(root_ast),
+                Err(diagnostics) => UsedFile::Invalid {
+                    content,
+                    diagnostics,
+                },
+            },
+            Err(err) => UsedFile::Unreadable(err.to_string()),
+        };
+
+        used_files.insert(path, used_file);
+    }
+
+    used_files
+}
@@ -0,0 +1,313 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    rc::Rc,
+};
+
+use serde_json::{json, Value};
+use zac::{
+    checker, diagnostic::Severity, lexer, loader, parser, prelude, span::Span, utils, Diagnostic,
+    FsLoader, Loader, Target,
+};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_REQUEST: i64 = -32600;
+
+/// A minimal language server speaking LSP over stdio. Open documents are kept
+/// in memory and re-parsed on every change, full text sync only.
+struct Server {
+    documents: HashMap<String, String>,
+    shutdown_requested: bool,
+}
+
+pub fn run() -> i32 {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout().lock();
+    let mut server = Server {
+        documents: HashMap::new(),
+        shutdown_requested: false,
+    };
+
+    loop {
+        let message = match read_message(&mut input) {
+            Ok(Some(message)) => message,
+            Ok(None) => return 1,
+            Err(err) => {
+                eprintln!("zac lsp: {err}");
+                return 1;
+            }
+        };
+
+        if message["method"] == "exit" {
+            return if server.shutdown_requested { 0 } else { 1 };
+        }
+
+        for response in server.handle(&message) {
+            if let Err(err) = write_message(&mut output, &response) {
+                eprintln!("zac lsp: {err}");
+                return 1;
+            }
+        }
+    }
+}
+
+impl Server {
+    /// Handles one incoming message and returns the messages to send back.
+    fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let id = message.get("id");
+
+        match (method, id) {
+            ("initialize", Some(id)) => vec![response(
+                id,
+                json!({
+                    "capabilities": {
+                        "textDocumentSync": { "openClose": true, "change": 1 }
+                    },
+                    "serverInfo": { "name": "zac", "version": env!("CARGO_PKG_VERSION") }
+                }),
+            )],
+            ("shutdown", Some(id)) => {
+                self.shutdown_requested = true;
+                vec![response(id, Value::Null)]
+            }
+            ("textDocument/didOpen", None) => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+
+                self.documents.insert(uri.to_string(), text.to_string());
+                vec![self.publish_diagnostics(uri)]
+            }
+            ("textDocument/didChange", None) => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+
+                match text {
+                    Some(text) => {
+                        self.documents.insert(uri.to_string(), text.to_string());
+                        vec![self.publish_diagnostics(uri)]
+                    }
+                    None => vec![],
+                }
+            }
+            ("textDocument/didClose", None) => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+                self.documents.remove(uri);
+                vec![self.publish_diagnostics(uri)]
+            }
+            (_, Some(id)) if self.shutdown_requested => vec![error_response(
+                id,
+                INVALID_REQUEST,
+                "The server is shutting down.",
+            )],
+            (_, Some(id)) => vec![error_response(
+                id,
+                METHOD_NOT_FOUND,
+                &format!("Unsupported method '{method}'."),
+            )],
+            _ => vec![],
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let diagnostics = match self.documents.get(uri) {
+            Some(content) => check(uri, content)
+                .iter()
+                .map(|diagnostic| to_lsp_diagnostic(uri, content, diagnostic))
+                .collect(),
+            None => vec![],
+        };
+
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics }
+        })
+    }
+}
+
+fn check(uri: &str, content: &str) -> Vec<Diagnostic> {
+    // The files the document uses are found relative to its path.
+    let path = file_path(uri);
+    let mut lexer = lexer::new(&path, content);
+
+    match parser::parse(&mut lexer) {
+        Ok(ast) => {
+            let used_files = loader::load_used(&path, &ast, &FsLoader);
+
+            checker::check(
+                &path,
+                &ast,
+                Some(prelude::root()),
+                Target::Module,
+                Rc::new(used_files),
+            )
+            .err()
+            .unwrap_or_default()
+        }
+        Err(diagnostics) => diagnostics,
+    }
+}
+
+/// The path of the file `uri` names, `/home/a b.zac` for `file:///home/a%20b.zac`.
+/// Other URIs, like the `untitled:` of unsaved documents, are kept as they are.
+fn file_path(uri: &str) -> String {
+    let Some(path) = uri.strip_prefix("file://") else {
+        return uri.to_string();
+    };
+
+    let path = path.strip_prefix("localhost").unwrap_or(path);
+    let mut bytes = vec![];
+    let mut rest = path.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(decoded) if byte == b'%' => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+
+    let path = String::from_utf8_lossy(&bytes).to_string();
+
+    // `/C:/src/a.zac` on Windows.
+    match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => path[1..].to_string(),
+        _ => path,
+    }
+}
+
+/// The `file://` URI of `path`, the reverse of `file_path`.
+fn file_uri(path: &str) -> String {
+    let encoded = path
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'.' | b'_' | b'~' | b':' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect::<String>();
+
+    if encoded.starts_with('/') {
+        format!("file://{encoded}")
+    } else {
+        format!("file:///{encoded}")
+    }
+}
+
+fn to_lsp_diagnostic(uri: &str, content: &str, diagnostic: &Diagnostic) -> Value {
+    let related_information = diagnostic
+        .notes
+        .iter()
+        .filter_map(|note| {
+            let span = note.span.as_ref()?;
+            let location = if note.file == diagnostic.file {
+                json!({ "uri": uri, "range": range(content, span) })
+            } else {
+                let note_content = FsLoader.load(&note.file).ok()?;
+
+                json!({ "uri": file_uri(&note.file), "range": range(&note_content, span) })
+            };
+
+            Some(json!({ "location": location, "message": note.message }))
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "range": range(content, &diagnostic.span),
+        "severity": match diagnostic.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+        },
+        "code": diagnostic.code,
+        "source": "zac",
+        "message": diagnostic.message,
+        "relatedInformation": related_information
+    })
+}
+
+fn range(content: &str, span: &Span) -> Value {
+    json!({
+        "start": position(content, span.from),
+        "end": position(content, span.to)
+    })
+}
+
+/// LSP positions count lines from zero and columns in UTF-16 code units.
+fn position(content: &str, pos: usize) -> Value {
+    let (line, character) = utils::line_utf16_col(content, pos);
+
+    json!({ "line": line, "character": character })
+}
+
+fn response(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error_response(id: &Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
+/// Reads one `Content-Length` framed message, `None` once the input is closed.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+
+    loop {
+        let mut header = String::new();
+
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+    let mut body = vec![0; content_length];
+
+    input.read_exact(&mut body)?;
+
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
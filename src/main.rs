@@ -1,59 +1,357 @@
 use std::{
-    fs::{self, DirEntry},
-    io,
-    path::Path,
+    env, fs, io,
+    path::{self, Path, PathBuf},
+    process,
+    sync::Arc,
     time::Instant,
 };
 
-pub mod ast;
-pub mod error_message;
-pub mod generator;
-pub mod lexer;
-mod parser;
-mod span;
-pub mod token;
-mod utils;
-
-fn visit_dirs(dir: &Path, cb: &dyn Fn(&DirEntry)) -> io::Result<()> {
-    if dir.is_dir() {
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_dir() {
-                visit_dirs(&path, cb)?;
-            } else {
-                cb(&entry);
-            }
+use serde_json::json;
+use zac::{diagnostic::Severity, generator, prelude, utils, Diagnostic, FsLoader};
+
+mod cli;
+mod lsp;
+
+fn visit_dirs(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+
+    entries.sort_by_key(|entry| entry.path());
+
+    for entry in entries {
+        let path = entry.path();
+
+        if path.is_dir() {
+            visit_dirs(&path, files)?;
+        } else if is_zac_file(&path) {
+            files.push(path);
         }
     }
+
     Ok(())
 }
 
+fn is_zac_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "zac")
+}
+
+/// Prints progress, errors and diagnostics in the format picked on the command line.
+struct Reporter {
+    message_format: cli::MessageFormat,
+    color: bool,
+}
+
+impl Reporter {
+    fn new(message_format: cli::MessageFormat) -> Reporter {
+        Reporter {
+            message_format,
+            color: message_format == cli::MessageFormat::Human && !no_color(),
+        }
+    }
+
+    fn progress(&self, message: &str) {
+        if self.message_format == cli::MessageFormat::Human {
+            println!("{message}");
+        }
+    }
+
+    /// Reports an error that is not about the source code, like a missing
+    /// input or a failed write, along with the file it is about if any.
+    fn error(&self, file: Option<&str>, message: &str) {
+        match self.message_format {
+            cli::MessageFormat::Human => match file {
+                Some(file) => eprintln!("{}: {file}: {message}", error_label(self.color)),
+                None => eprintln!("{}: {message}", error_label(self.color)),
+            },
+            cli::MessageFormat::Json => println!(
+                "{}",
+                json!({
+                    "file": file,
+                    "range": null,
+                    "start": null,
+                    "end": null,
+                    "severity": Severity::Error.name(),
+                    "code": null,
+                    "message": message,
+                    "notes": [],
+                })
+            ),
+        }
+    }
+
+    fn diagnostic(&self, diagnostic: &Diagnostic, content: &str) {
+        match self.message_format {
+            cli::MessageFormat::Human => {
+                eprintln!("{}", diagnostic.render(content, &FsLoader, self.color))
+            }
+            cli::MessageFormat::Json => println!("{}", diagnostic.to_json(content, &FsLoader)),
+        }
+    }
+}
+
+fn no_color() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+fn error_label(color: bool) -> &'static str {
+    if color {
+        "\x1b[91mError\x1b[0m"
+    } else {
+        "Error"
+    }
+}
+
 fn main() {
-    println!("");
-    println!("compiling...");
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let command = match cli::parse(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}: {message}", error_label(!no_color()));
+            eprintln!();
+            eprintln!("{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    let exit_code = match command {
+        cli::Command::Build(options) => build(&options),
+        cli::Command::Fmt(options) => fmt(&options),
+        cli::Command::Lsp => lsp::run(),
+        cli::Command::Help => {
+            println!("{}", cli::USAGE);
+            0
+        }
+        cli::Command::Version => {
+            println!("zac {}", env!("CARGO_PKG_VERSION"));
+            0
+        }
+    };
+
+    process::exit(exit_code);
+}
 
+fn build(options: &cli::BuildOptions) -> i32 {
+    let reporter = Reporter::new(options.message_format);
     let start = Instant::now();
-    let _ = visit_dirs(Path::new("./samples"), &|d| {
-        let file_path = crate::utils::normalize_path(&d.path())
-            .to_string_lossy()
-            .to_string();
+    let (sources, mut errors) = collect_sources(&reporter, &options.inputs);
 
-        // if !filepath.contains("router") {
-        //     return;
-        // }
+    let output_paths = match output_paths(options) {
+        Ok(output_paths) => output_paths,
+        Err(err) => {
+            reporter.error(None, &err.to_string());
+            return 1;
+        }
+    };
+
+    reporter.progress("");
+    reporter.progress("compiling...");
+
+    for source in &sources {
+        errors += compile_file(&reporter, &output_paths, source, options);
+    }
+
+    if options.prelude && !write_prelude(&reporter, &output_paths) {
+        errors += 1;
+    }
 
-        if !file_path.contains(".zac") {
-        } else {
-            println!("compiling {}", file_path);
-            let file_content = String::from_utf8_lossy(&fs::read(d.path()).unwrap()).to_string();
-            let mut lexer = lexer::new(&file_path, &file_content);
-            let ast = parser::parse(&mut lexer);
-            // println!("{ast:#?}");
-            // checker::check(&filepath, &content, &ast);
-            generator::generate(file_path, file_content, &ast)
-        }
-    });
     let duration = start.elapsed();
-    println!("compilation done in {duration:?}");
+
+    if errors > 0 {
+        reporter.progress(&format!(
+            "compilation failed with {errors} error(s) in {duration:?}"
+        ));
+        1
+    } else {
+        reporter.progress(&format!("compilation done in {duration:?}"));
+        0
+    }
+}
+
+/// Finds the .zac files among `inputs`, reporting the inputs that cannot be used
+/// and how many there are.
+fn collect_sources(reporter: &Reporter, inputs: &[PathBuf]) -> (Vec<PathBuf>, usize) {
+    let mut failures = 0;
+    let mut sources = vec![];
+
+    for input in inputs {
+        if input.is_dir() {
+            if let Err(err) = visit_dirs(input, &mut sources) {
+                reporter.error(None, &format!("could not read {}: {err}", input.display()));
+                failures += 1;
+            }
+        } else if !input.exists() {
+            reporter.error(None, &format!("{} does not exist", input.display()));
+            failures += 1;
+        } else if !is_zac_file(input) {
+            reporter.error(None, &format!("{} is not a .zac file", input.display()));
+            failures += 1;
+        } else {
+            sources.push(input.clone());
+        }
+    }
+
+    (sources, failures)
+}
+
+fn output_paths(options: &cli::BuildOptions) -> io::Result<generator::OutputPaths> {
+    let source_root = match &options.root {
+        Some(root) => root.clone(),
+        None => {
+            let dirs = options
+                .inputs
+                .iter()
+                .map(|input| {
+                    if input.is_dir() {
+                        path::absolute(input)
+                    } else {
+                        path::absolute(input.join(".."))
+                    }
+                })
+                .map(|dir| dir.map(|dir| utils::normalize_path(&dir)))
+                .collect::<io::Result<Vec<_>>>()?;
+
+            utils::common_ancestor(&dirs)
+        }
+    };
+
+    match &options.out_file {
+        Some(out_file) => {
+            let out_dir = match out_file.parent() {
+                Some(parent) if parent != Path::new("") => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+            let out_file_name = PathBuf::from(out_file.file_name().unwrap_or_default());
+
+            Ok(generator::OutputPaths::new(source_root, out_dir).with_out_file(out_file_name))
+        }
+        None => Ok(generator::OutputPaths::new(
+            source_root,
+            options.out_dir.clone(),
+        )),
+    }
+}
+
+/// Compiles `source` and writes the result, returns how many errors were reported.
+fn compile_file(
+    reporter: &Reporter,
+    output_paths: &generator::OutputPaths,
+    source: &Path,
+    options: &cli::BuildOptions,
+) -> usize {
+    let file_path = utils::normalize_path(source).to_string_lossy().to_string();
+
+    reporter.progress(&format!("compiling {}", file_path));
+
+    let file_content = match fs::read(source) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+        Err(err) => {
+            reporter.error(Some(&file_path), &err.to_string());
+            return 1;
+        }
+    };
+
+    let prelude = if options.prelude {
+        match output_paths.prelude_specifier(source) {
+            Ok(specifier) => Some(specifier),
+            Err(err) => {
+                reporter.error(Some(&file_path), &err.to_string());
+                return 1;
+            }
+        }
+    } else {
+        None
+    };
+
+    let compile_options = zac::Options {
+        prelude,
+        target: options.target,
+        loader: Arc::new(FsLoader),
+    };
+
+    match zac::compile(&file_path, &file_content, &compile_options) {
+        Ok(output) => {
+            match generator::write(output_paths, &file_path, &output.code, &output.source_map) {
+                Ok(_) => 0,
+                Err(err) => {
+                    reporter.error(Some(&file_path), &err.to_string());
+                    1
+                }
+            }
+        }
+        Err(diagnostics) => {
+            for diagnostic in &diagnostics {
+                reporter.diagnostic(diagnostic, &file_content);
+            }
+            diagnostics.len()
+        }
+    }
+}
+
+/// Writes the core library the generated files import, `zac_core.js`.
+fn write_prelude(reporter: &Reporter, output_paths: &generator::OutputPaths) -> bool {
+    let result = match prelude::generate() {
+        Ok(code) => generator::write_prelude(output_paths, &code),
+        Err(diagnostic) => {
+            reporter.error(Some(&diagnostic.file), &diagnostic.message);
+            return false;
+        }
+    };
+
+    match result {
+        Ok(_) => true,
+        Err(err) => {
+            reporter.error(Some(prelude::FILE_NAME), &err.to_string());
+            false
+        }
+    }
+}
+
+/// Rewrites every input in the canonical style, or with `--check` only lists
+/// the files that are not formatted yet.
+fn fmt(options: &cli::FmtOptions) -> i32 {
+    let reporter = Reporter::new(cli::MessageFormat::Human);
+    let (sources, mut failures) = collect_sources(&reporter, &options.inputs);
+    let mut unformatted = 0;
+
+    for source in &sources {
+        let file_path = utils::normalize_path(source).to_string_lossy().to_string();
+
+        let file_content = match fs::read_to_string(source) {
+            Ok(content) => content,
+            Err(err) => {
+                reporter.error(Some(&file_path), &err.to_string());
+                failures += 1;
+                continue;
+            }
+        };
+
+        let formatted = match zac::format(&file_path, &file_content) {
+            Ok(formatted) => formatted,
+            Err(diagnostics) => {
+                for diagnostic in diagnostics {
+                    reporter.diagnostic(&diagnostic, &file_content);
+                }
+                failures += 1;
+                continue;
+            }
+        };
+
+        if formatted == file_content {
+            continue;
+        }
+
+        if options.check {
+            println!("{file_path}");
+            unformatted += 1;
+        } else if let Err(err) = fs::write(source, formatted) {
+            reporter.error(Some(&file_path), &err.to_string());
+            failures += 1;
+        }
+    }
+
+    if failures > 0 || unformatted > 0 {
+        1
+    } else {
+        0
+    }
 }
@@ -1,14 +1,14 @@
-use super::context::ParsingContext;
+use super::context::{ParseResult, ParsingContext};
 use super::expression;
 use crate::ast;
 use crate::span::Span;
 use crate::token::TokenKind;
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::InitArray {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::InitArray> {
     let span_from = ctx.get_curr_token().span().from;
 
-    ctx.eat(TokenKind::LSqrBr);
-    ctx.eat_all_newlines();
+    ctx.eat(TokenKind::LSqrBr)?;
+    ctx.eat_all_newlines()?;
 
     let mut items = vec![];
 
@@ -17,29 +17,29 @@ pub fn parse(ctx: &mut ParsingContext) -> ast::InitArray {
             break;
         }
 
-        items.push(parse_item(ctx));
+        items.push(parse_item(ctx)?);
     }
 
-    ctx.eat(TokenKind::RSqrBr);
+    ctx.eat(TokenKind::RSqrBr)?;
 
     let span_to = ctx.get_prev_token().span().to;
 
-    ast::InitArray {
+    Ok(ast::InitArray {
         items,
         span: Span::new(span_from, span_to),
-    }
+    })
 }
 
-pub fn parse_item(ctx: &mut ParsingContext) -> ast::Expr {
-    let ast = expression::parse(ctx);
+pub fn parse_item(ctx: &mut ParsingContext) -> ParseResult<ast::Expr> {
+    let ast = expression::parse(ctx)?;
 
-    ctx.eat_all_newlines();
+    ctx.eat_all_newlines()?;
 
     if ctx.get_curr_token().kind() != TokenKind::RSqrBr {
-        ctx.eat(TokenKind::Comma);
+        ctx.eat(TokenKind::Comma)?;
     }
 
-    ctx.eat_all_newlines();
+    ctx.eat_all_newlines()?;
 
-    return ast;
+    Ok(ast)
 }
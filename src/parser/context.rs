@@ -1,153 +1,354 @@
-use crate::error_message::ErrorMessage;
+use crate::diagnostic::{code, Diagnostic};
 use crate::lexer::Lexer;
+use crate::span::Span;
 use crate::token::{Token, TokenKind};
+
+pub type ParseResult<T> = Result<T, Diagnostic>;
+
 pub struct ParsingContext<'l> {
     pub lexer: &'l mut Lexer,
     pub prev_token: Token,
     curr_token: Token,
     anon_counter: usize,
+    pub errors: Vec<Diagnostic>,
+    /// Whether a `{` can continue an expression, as in the record literal
+    /// `Id { ... }` or the function `(a) { ... }`. It cannot in the condition
+    /// of an `if` or the value of a `match`, where the brace opens the block.
+    pub braces: bool,
+    /// Whether a `.` starting the next line continues the expression. It does
+    /// not after the expression of a `match` arm, where it starts the next
+    /// pattern.
+    pub line_chains: bool,
+    /// Whether the fn being parsed uses `.await`, the top level of the file
+    /// outside of any fn.
+    pub awaits: bool,
 }
 
 impl<'l> ParsingContext<'l> {
     pub fn new(lexer: &'l mut Lexer) -> Self {
-        let token = lexer.get_next_token(false, false);
+        let token = Token::default();
 
-        ParsingContext {
+        let mut ctx = ParsingContext {
             lexer,
             prev_token: token.clone(),
             curr_token: token,
             anon_counter: 1,
+            errors: vec![],
+            braces: true,
+            line_chains: true,
+            awaits: false,
+        };
+
+        ctx.skip_token();
+        ctx.prev_token = ctx.get_curr_token();
+        ctx
+    }
+
+    /// Runs `parse` and, if it fails, records the error and skips ahead to the
+    /// next statement boundary so that the rest of the file still gets parsed.
+    /// The span of the skipped source is returned in place of the node.
+    pub fn parse_or_recover<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> Result<T, Span> {
+        let span_from = self.get_curr_token().span().from;
+
+        match parse(self) {
+            Ok(node) => Ok(node),
+            Err(diagnostic) => {
+                self.report(diagnostic);
+
+                if self.get_curr_token().span().from == span_from && self.is_not_eof() {
+                    self.skip_token();
+                }
+
+                self.synchronize();
+
+                Err(Span::new(span_from, self.get_curr_token().span().from))
+            }
+        }
+    }
+
+    /// Runs `parse` on `from..to` of the file, the expression interpolated in
+    /// a string for instance, which must be consumed as a whole. Running out of
+    /// tokens is reported on the `}` closing the slice, not as the end of the
+    /// file.
+    pub fn parse_slice<T>(
+        &mut self,
+        from: usize,
+        to: usize,
+        parse: impl FnOnce(&mut ParsingContext) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let mut lexer = self.lexer.slice(from, to);
+        let mut ctx = ParsingContext::new(&mut lexer);
+
+        ctx.anon_counter = self.anon_counter;
+
+        let result = parse(&mut ctx).and_then(|node| {
+            ctx.eat_all_newlines()?;
+
+            if ctx.is_not_eof() {
+                return ctx.throw_unexpected_token();
+            }
+
+            Ok(node)
+        });
+
+        let result = result.map_err(|error| {
+            if error.span.from != to {
+                return error;
+            }
+
+            Diagnostic {
+                message: "Unexpected end of interpolation.".to_string(),
+                span: Span::new(to, to + 1),
+                ..error
+            }
+        });
+
+        self.anon_counter = ctx.anon_counter;
+        self.awaits |= ctx.awaits;
+        self.errors.append(&mut ctx.errors);
+
+        result
+    }
+
+    pub fn without_braces<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let braces = self.braces;
+
+        self.braces = false;
+
+        let result = parse(self);
+
+        self.braces = braces;
+        result
+    }
+
+    pub fn without_line_chains<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let line_chains = self.line_chains;
+
+        self.line_chains = false;
+
+        let result = parse(self);
+
+        self.line_chains = line_chains;
+        result
+    }
+
+    /// Records `diagnostic` unless an error was already reported at the same
+    /// position, the second one only follows from the first.
+    fn report(&mut self, diagnostic: Diagnostic) {
+        let is_duplicate = self
+            .errors
+            .iter()
+            .any(|error| error.span == diagnostic.span);
+
+        if !is_duplicate {
+            self.errors.push(diagnostic);
+        }
+    }
+
+    /// Skips tokens until the next newline, `}` or top-level keyword that is not
+    /// nested inside a block opened while skipping.
+    fn synchronize(&mut self) {
+        let mut depth = 0;
+
+        while self.is_not_eof() {
+            match self.get_curr_token().kind() {
+                TokenKind::NewLine if depth == 0 => {
+                    self.skip_token();
+                    return;
+                }
+                TokenKind::RBrace | TokenKind::Fn | TokenKind::Mod | TokenKind::Let
+                    if depth == 0 =>
+                {
+                    return;
+                }
+                TokenKind::RBrace => depth -= 1,
+                TokenKind::LBrace => depth += 1,
+                _ => {}
+            }
+
+            self.skip_token();
+        }
+    }
+
+    /// Moves to the next token no matter what the current one is, reporting and
+    /// stepping over any character the lexer cannot handle.
+    fn skip_token(&mut self) {
+        loop {
+            match self.lexer.get_next_token(false, false) {
+                Ok(token) => {
+                    self.prev_token = self.get_curr_token();
+                    self.curr_token = token;
+                    return;
+                }
+                Err(diagnostic) => {
+                    self.lexer.pos = diagnostic.span.to;
+                    self.report(diagnostic);
+                }
+            }
         }
     }
 
-    pub fn eat(&mut self, target_kind: TokenKind) {
+    pub fn eat(&mut self, target_kind: TokenKind) -> ParseResult<()> {
         if TokenKind::from(self.get_curr_token()) == target_kind {
-            let next_token = self.lexer.get_next_token(false, false);
+            let next_token = self.lexer.get_next_token(false, false)?;
             self.prev_token = self.get_curr_token();
             self.curr_token = next_token;
+            Ok(())
         } else {
-            self.throw_unexpected_token_with_expecting(&target_kind);
+            self.throw_unexpected_token_with_expecting(&target_kind)
         }
     }
 
-    pub fn eat_without_consuming_jsx(&mut self, target_kind: TokenKind) {
+    pub fn eat_without_consuming_jsx(&mut self, target_kind: TokenKind) -> ParseResult<()> {
         if TokenKind::from(self.get_curr_token()) == target_kind {
-            let next_token = self.lexer.get_next_token(true, false);
+            let next_token = self.lexer.get_next_token(true, false)?;
             self.prev_token = self.get_curr_token();
             self.curr_token = next_token;
+            Ok(())
         } else {
-            self.throw_unexpected_token_with_expecting(&target_kind);
+            self.throw_unexpected_token_with_expecting(&target_kind)
         }
     }
 
-    pub fn eat_jsx(&mut self, target_kind: TokenKind) {
+    pub fn eat_jsx(&mut self, target_kind: TokenKind) -> ParseResult<()> {
         if TokenKind::from(self.get_curr_token()) == target_kind {
-            let next_token = self.lexer.get_next_token(false, true);
+            let next_token = self.lexer.get_next_token(false, true)?;
             self.prev_token = self.get_curr_token();
             self.curr_token = next_token;
+            Ok(())
         } else {
-            self.throw_unexpected_token_with_expecting(&target_kind);
+            self.throw_unexpected_token_with_expecting(&target_kind)
         }
     }
 
-    pub fn eat_all_newlines(&mut self) {
-        match TokenKind::from(self.get_curr_token()) {
-            tok if tok != TokenKind::NewLine => {
-                return;
-            }
-            TokenKind::NewLine => {
-                let next_token = self.lexer.get_next_token(false, false);
-                self.prev_token = self.get_curr_token();
-                self.curr_token = next_token;
-            }
-            _ => self.throw_unexpected_token_with_expecting(&TokenKind::NewLine),
+    pub fn eat_all_newlines(&mut self) -> ParseResult<()> {
+        while self.get_curr_token().kind() == TokenKind::NewLine {
+            let next_token = self.lexer.get_next_token(false, false)?;
+            self.prev_token = self.get_curr_token();
+            self.curr_token = next_token;
         }
+
+        Ok(())
     }
 
-    pub fn eat_all_newlines_jsx(&mut self) {
-        match TokenKind::from(self.get_curr_token()) {
-            tok if tok != TokenKind::NewLine => {
-                return;
-            }
-            TokenKind::NewLine => {
-                let next_token = self.lexer.get_next_token(false, true);
-                self.prev_token = self.get_curr_token();
-                self.curr_token = next_token;
-            }
-            _ => self.throw_unexpected_token_with_expecting(&TokenKind::NewLine),
+    pub fn eat_all_newlines_jsx(&mut self) -> ParseResult<()> {
+        while self.get_curr_token().kind() == TokenKind::NewLine {
+            let next_token = self.lexer.get_next_token(false, true)?;
+            self.prev_token = self.get_curr_token();
+            self.curr_token = next_token;
         }
+
+        Ok(())
+    }
+
+    pub fn peek_ahead(&mut self) -> ParseResult<Token> {
+        let (_, token) = self.lexer.peek_ahead(self.lexer.pos, false, false)?;
+
+        Ok(token)
     }
 
-    pub fn peek_ahead_ignoring_newlines(&mut self) -> Token {
+    pub fn peek_ahead_ignoring_newlines(&mut self) -> ParseResult<Token> {
         let mut pos = self.lexer.pos;
 
         loop {
-            let (new_pos, token) = self.lexer.peek_ahead(pos, false, false);
+            let (new_pos, token) = self.lexer.peek_ahead(pos, false, false)?;
 
             pos = new_pos;
 
             if token.kind() != TokenKind::NewLine {
-                return token;
+                return Ok(token);
+            }
+        }
+    }
+
+    /// Whether the current `(` opens the arguments of a function like
+    /// `(a, b: Number) { ... }` rather than a parenthesized expression.
+    pub fn at_fn_args(&mut self) -> ParseResult<bool> {
+        let mut pos = self.lexer.pos;
+
+        loop {
+            let Ok((new_pos, token)) = self.lexer.peek_ahead(pos, true, false) else {
+                return Ok(false);
+            };
+
+            pos = new_pos;
+
+            match token.kind() {
+                TokenKind::RParen => break,
+                TokenKind::Id
+                | TokenKind::DblColon
+                | TokenKind::Comma
+                | TokenKind::Lt
+                | TokenKind::Gt
+                | TokenKind::Dot
+                | TokenKind::NewLine => {}
+                _ => return Ok(false),
             }
         }
+
+        Ok(matches!(
+            self.lexer.peek_ahead(pos, true, false),
+            Ok((_, Token::LBrace(_) | Token::DblColon(_)))
+        ))
     }
 
-    pub fn throw_unexpected_token_with_expecting(&mut self, kind: &TokenKind) {
-        let filepath = self.lexer.filepath.clone();
-        let content = self.lexer.content.clone();
+    pub fn throw_unexpected_token_with_expecting<T>(&mut self, kind: &TokenKind) -> ParseResult<T> {
         let current_token = self.get_curr_token();
         let message = format!(
-            "Unexpected token {:?}, was expecting token with type {:?}.",
-            &current_token.value(),
+            "Unexpected {}, was expecting token with type {:?}.",
+            describe(&current_token),
             kind
         );
-        let source_span = current_token.span();
-
-        ErrorMessage::new(filepath, content, message, source_span.from).print();
 
-        panic!()
+        Err(self
+            .error_at(message, &current_token)
+            .with_code(code::UNEXPECTED_TOKEN))
     }
 
-    pub fn throw_unexpected_token(&mut self) {
-        let filepath = self.lexer.filepath.clone();
-        let content = self.lexer.content.clone();
+    pub fn throw_unexpected_token<T>(&mut self) -> ParseResult<T> {
         let current_token = self.get_curr_token();
-        let message = format!("Unexpected token {:?}.", current_token.value());
-        let source_span = current_token.span();
-        ErrorMessage::new(filepath, content, message, source_span.from).print();
+        let message = format!("Unexpected {}.", describe(&current_token));
 
-        panic!()
+        Err(self
+            .error_at(message, &current_token)
+            .with_code(code::UNEXPECTED_TOKEN))
     }
 
-    pub fn throw_reserved_keywords(&mut self, value: &str) {
-        let filepath = self.lexer.filepath.clone();
-        let content = self.lexer.content.clone();
+    pub fn throw_reserved_keywords<T>(&mut self, value: &str) -> ParseResult<T> {
         let current_token = self.get_curr_token();
         let message = format!("Reserved keywords {:?} cannot be used here.", value);
-        let source_span = current_token.span();
 
-        ErrorMessage::new(filepath, content, message, source_span.from).print();
+        Err(self
+            .error_at(message, &current_token)
+            .with_code(code::RESERVED_KEYWORD))
+    }
 
-        panic!()
+    pub fn throw_custom<T>(&mut self, message: &str) -> ParseResult<T> {
+        Err(self.error(message))
     }
 
-    pub fn throw_custom(&mut self, message: &str) {
-        let filepath = self.lexer.filepath.clone();
-        let content = self.lexer.content.clone();
+    pub fn error(&self, message: &str) -> Diagnostic {
         let current_token = self.get_curr_token();
-        let source_span = current_token.span();
 
-        ErrorMessage::new(filepath, content, message.to_string(), source_span.from).print();
+        self.error_at(message.to_string(), &current_token)
+            .with_code(code::SYNTAX_ERROR)
+    }
 
-        panic!()
+    fn error_at(&self, message: String, token: &Token) -> Diagnostic {
+        Diagnostic::error(self.lexer.filepath.clone(), message, token.span())
     }
 
     pub fn is_not_eof(&mut self) -> bool {
-        if self.curr_token.kind() != TokenKind::Eof {
-            true
-        } else {
-            false
-        }
+        self.curr_token.kind() != TokenKind::Eof
     }
 
     pub fn get_prev_token(&self) -> Token {
@@ -164,3 +365,11 @@ impl<'l> ParsingContext<'l> {
         format!("anon_{}", v)
     }
 }
+
+/// How `token` reads in messages, `token "fn"` or `end of file`.
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Eof(_) => "end of file".to_string(),
+        token => format!("token {:?}", token.value()),
+    }
+}
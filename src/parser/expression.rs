@@ -1,46 +1,185 @@
 use crate::ast;
+use crate::diagnostic::Diagnostic;
+use crate::span::{Span, Spanned};
 use crate::token::TokenKind;
 
-use super::context::ParsingContext;
+use super::context::{ParseResult, ParsingContext};
 use super::{
     array, function, function_call, identifier, jsx_element, literal_boolean, literal_js,
-    literal_number, literal_string, member_access,
+    literal_number, literal_string, member_access, record, statement_if, statement_match, variant,
 };
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::Expr {
-    ctx.eat_all_newlines();
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Expr> {
+    ctx.eat_all_newlines()?;
 
+    let expr = parse_binary(ctx, 0)?;
+
+    ctx.eat_all_newlines()?;
+
+    Ok(expr)
+}
+
+/// Parses the operators following `left`, for statements that start with an
+/// operand parsed on its own.
+pub fn parse_rest(ctx: &mut ParsingContext, left: ast::Expr) -> ParseResult<ast::Expr> {
+    let expr = parse_operators(ctx, left, 0)?;
+
+    ctx.eat_all_newlines()?;
+
+    Ok(expr)
+}
+
+/// Parses a single operand, without the operators following it.
+pub fn parse_operand(ctx: &mut ParsingContext) -> ParseResult<ast::Expr> {
+    parse_unary(ctx)
+}
+
+/// Parses operands joined by operators binding tighter than `min_precedence`.
+fn parse_binary(ctx: &mut ParsingContext, min_precedence: u8) -> ParseResult<ast::Expr> {
+    let left = parse_unary(ctx)?;
+
+    parse_operators(ctx, left, min_precedence)
+}
+
+fn parse_operators(
+    ctx: &mut ParsingContext,
+    mut left: ast::Expr,
+    min_precedence: u8,
+) -> ParseResult<ast::Expr> {
+    while let Some(op) = operator_ahead(ctx)? {
+        if op.precedence() <= min_precedence {
+            break;
+        }
+
+        ctx.eat(ctx.get_curr_token().kind())?;
+        ctx.eat_all_newlines()?;
+
+        let right = parse_binary(ctx, op.precedence())?;
+        let span = Span::new(left.span().from, right.span().to);
+        let binary = ast::Binary {
+            op,
+            left,
+            right,
+            span,
+        };
+
+        if op == ast::BinaryOp::Pipe && binary.piped_call().is_none() {
+            return Err(Diagnostic {
+                span: binary.right.span(),
+                ..ctx.error("Only a function can be piped into, write `value |> f` or `value |> f(_, 2)` with one `_` where the value goes.")
+            });
+        }
+
+        left = binary.into();
+    }
+
+    Ok(left)
+}
+
+/// The operator continuing the expression, if any. A `|>` can start the next
+/// line, the line breaks before it are eaten.
+pub fn operator_ahead(ctx: &mut ParsingContext) -> ParseResult<Option<ast::BinaryOp>> {
+    if ctx.get_curr_token().kind() == TokenKind::NewLine
+        && ctx.peek_ahead_ignoring_newlines()?.kind() == TokenKind::Pipe
+    {
+        ctx.eat_all_newlines()?;
+    }
+
+    Ok(binary_op(ctx.get_curr_token().kind()))
+}
+
+fn binary_op(kind: TokenKind) -> Option<ast::BinaryOp> {
+    let op = match kind {
+        TokenKind::Plus => ast::BinaryOp::Add,
+        TokenKind::Minus => ast::BinaryOp::Sub,
+        TokenKind::Multiply => ast::BinaryOp::Mul,
+        TokenKind::Divide => ast::BinaryOp::Div,
+        TokenKind::EqEq => ast::BinaryOp::Eq,
+        TokenKind::NotEq => ast::BinaryOp::Ne,
+        TokenKind::Lt => ast::BinaryOp::Lt,
+        TokenKind::Gt => ast::BinaryOp::Gt,
+        TokenKind::LtEq => ast::BinaryOp::Le,
+        TokenKind::GtEq => ast::BinaryOp::Ge,
+        TokenKind::AndAnd => ast::BinaryOp::And,
+        TokenKind::OrOr => ast::BinaryOp::Or,
+        TokenKind::Pipe => ast::BinaryOp::Pipe,
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+fn parse_unary(ctx: &mut ParsingContext) -> ParseResult<ast::Expr> {
+    let op = match ctx.get_curr_token().kind() {
+        TokenKind::Minus => ast::UnaryOp::Neg,
+        TokenKind::Bang => ast::UnaryOp::Not,
+        _ => return parse_primary(ctx),
+    };
+
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(ctx.get_curr_token().kind())?;
+
+    let expr = parse_unary(ctx)?;
+    let span = Span::new(span_from, expr.span().to);
+
+    Ok(ast::Unary { op, expr, span }.into())
+}
+
+/// Parses an operand along with the members accessed on it.
+pub fn parse_primary(ctx: &mut ParsingContext) -> ParseResult<ast::Expr> {
     let mut expr = match TokenKind::from(ctx.get_curr_token()) {
-        TokenKind::Str => literal_string::parse(ctx).into(),
-        TokenKind::Boolean => literal_boolean::parse(ctx).into(),
-        TokenKind::Numeric => literal_number::parse(ctx).into(),
-        TokenKind::Js => literal_js::parse(ctx).into(),
-        TokenKind::LParen => function::parse(ctx, true).into(),
-        TokenKind::JsxOpen => jsx_element::parse(ctx, false).into(),
+        TokenKind::Str => literal_string::parse(ctx)?.into(),
+        TokenKind::Boolean => literal_boolean::parse(ctx)?.into(),
+        TokenKind::Numeric => literal_number::parse(ctx)?.into(),
+        TokenKind::Js => literal_js::parse(ctx)?.into(),
+        TokenKind::LParen if ctx.braces && ctx.at_fn_args()? => function::parse(ctx, true)?.into(),
+        TokenKind::LParen => parse_group(ctx)?,
+        TokenKind::JsxOpen => jsx_element::parse(ctx, false)?.into(),
+        TokenKind::If => statement_if::parse(ctx)?.into(),
+        TokenKind::Dot => variant::parse(ctx)?.into(),
+        TokenKind::Id if statement_match::is_match(ctx)? => statement_match::parse(ctx)?.into(),
         TokenKind::Id => {
-            let id = identifier::parse(ctx);
+            let id = identifier::parse_name(ctx)?;
 
             let expr = match ctx.get_curr_token().kind() {
-                TokenKind::LParen => function_call::parse(ctx, id).into(),
+                TokenKind::LParen => function_call::parse(ctx, id)?.into(),
+                TokenKind::LBrace if ctx.braces => record::parse(ctx, id)?.into(),
                 _ => id.into(),
             };
 
-            ctx.eat_all_newlines();
+            if ctx.line_chains && ctx.peek_ahead_ignoring_newlines()?.kind() == TokenKind::Dot {
+                ctx.eat_all_newlines()?;
+            }
 
             expr
         }
-        TokenKind::LSqrBr => ast::Expr::InitArray(array::parse(ctx)),
-        _ => {
-            ctx.throw_unexpected_token();
-            panic!()
-        }
+        TokenKind::LSqrBr => ast::Expr::InitArray(array::parse(ctx)?),
+        _ => return ctx.throw_unexpected_token(),
     };
 
     if ctx.get_curr_token().kind() == TokenKind::Dot {
-        expr = member_access::parse(ctx, expr).into();
+        expr = member_access::parse(ctx, expr)?.into();
     }
 
-    ctx.eat_all_newlines();
+    Ok(expr)
+}
+
+/// A parenthesized expression, the braces are allowed again inside.
+fn parse_group(ctx: &mut ParsingContext) -> ParseResult<ast::Expr> {
+    ctx.eat(TokenKind::LParen)?;
+
+    let braces = ctx.braces;
+
+    ctx.braces = true;
+
+    let expr = parse(ctx);
+
+    ctx.braces = braces;
+
+    let expr = expr?;
+
+    ctx.eat(TokenKind::RParen)?;
 
-    expr
+    Ok(expr)
 }
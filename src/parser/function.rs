@@ -5,19 +5,20 @@ use crate::{
 };
 
 use super::{
-    context::ParsingContext, function_call, identifier, literal_js, member_access, statement_if,
-    statement_let, statement_return,
+    context::{ParseResult, ParsingContext},
+    expression, function_call, identifier, literal_js, member_access, record, statement_if,
+    statement_let, statement_match, statement_return,
 };
 
-pub fn parse(ctx: &mut ParsingContext, anonymous: bool) -> ast::Fn {
+pub fn parse(ctx: &mut ParsingContext, anonymous: bool) -> ParseResult<ast::Fn> {
     let span_from = ctx.get_curr_token().span().from;
 
     if !anonymous {
-        ctx.eat(TokenKind::Fn);
+        ctx.eat(TokenKind::Fn)?;
     }
 
     let id = if !anonymous {
-        identifier::parse(ctx)
+        identifier::parse(ctx)?
     } else {
         ast::Ident {
             string: ctx.get_new_anon_name(),
@@ -29,55 +30,56 @@ pub fn parse(ctx: &mut ParsingContext, anonymous: bool) -> ast::Fn {
         }
     };
 
-    ctx.eat(TokenKind::LParen);
+    let (args, output) = parse_signature(ctx)?;
 
-    let args = parse_args(ctx);
+    ctx.eat(TokenKind::LBrace)?;
 
-    ctx.eat(TokenKind::RParen);
+    let awaits = std::mem::replace(&mut ctx.awaits, false);
+    let stmts = parse_statements(ctx)?;
+    let is_async = std::mem::replace(&mut ctx.awaits, awaits);
 
-    let output;
-
-    if ctx.get_curr_token().kind() == TokenKind::DblColon {
-        ctx.eat(TokenKind::DblColon);
-
-        let mut id = identifier::parse(ctx);
-
-        while ctx.get_curr_token().kind() == TokenKind::Dot {
-            ctx.eat(TokenKind::Dot);
-            let id_part = identifier::parse(ctx);
-            id = id.concat(id_part);
-        }
-
-        output = ast::Type::Ident(id)
-    } else {
-        output = ast::Type::Default;
-    }
-
-    ctx.eat(TokenKind::LBrace);
-
-    let stmts = parse_statements(ctx);
-
-    ctx.eat(TokenKind::RBrace);
+    ctx.eat(TokenKind::RBrace)?;
 
     let span_to = ctx.get_curr_token().span().from;
 
-    ast::Fn {
+    Ok(ast::Fn {
         args,
         id,
         anonymous,
+        external: false,
+        public: false,
+        is_async,
         stmts,
         output,
         span: Span::new(span_from, span_to),
-    }
+    })
+}
+
+/// Parses the arguments and the output of a fn, `(a: Number, b): String`.
+pub fn parse_signature(ctx: &mut ParsingContext) -> ParseResult<(Vec<ast::FnArg>, ast::Type)> {
+    ctx.eat(TokenKind::LParen)?;
+
+    let args = parse_args(ctx)?;
+
+    ctx.eat(TokenKind::RParen)?;
+
+    let output = if ctx.get_curr_token().kind() == TokenKind::DblColon {
+        ctx.eat(TokenKind::DblColon)?;
+        identifier::parse_type(ctx)?
+    } else {
+        ast::Type::Default
+    };
+
+    Ok((args, output))
 }
 
-fn parse_args(ctx: &mut ParsingContext) -> Vec<ast::FnArg> {
+fn parse_args(ctx: &mut ParsingContext) -> ParseResult<Vec<ast::FnArg>> {
     let mut args = vec![];
 
-    ctx.eat_all_newlines();
+    ctx.eat_all_newlines()?;
 
     while ctx.is_not_eof() {
-        ctx.eat_all_newlines();
+        ctx.eat_all_newlines()?;
 
         if let Token::RParen(_) = ctx.get_curr_token() {
             break;
@@ -85,19 +87,11 @@ fn parse_args(ctx: &mut ParsingContext) -> Vec<ast::FnArg> {
 
         let span_from = ctx.get_curr_token().span().from;
 
-        let id = identifier::parse(ctx);
+        let id = identifier::parse(ctx)?;
 
         let input = if ctx.get_curr_token().kind() == TokenKind::DblColon {
-            ctx.eat(TokenKind::DblColon);
-            let mut id = identifier::parse(ctx);
-
-            while ctx.get_curr_token().kind() == TokenKind::Dot {
-                ctx.eat(TokenKind::Dot);
-                let id_part = identifier::parse(ctx);
-                id = id.concat(id_part);
-            }
-
-            ast::Type::Ident(id)
+            ctx.eat(TokenKind::DblColon)?;
+            identifier::parse_type(ctx)?
         } else {
             ast::Type::Default
         };
@@ -113,65 +107,109 @@ fn parse_args(ctx: &mut ParsingContext) -> Vec<ast::FnArg> {
         if !(ctx.get_curr_token().kind() == TokenKind::Comma
             || ctx.get_curr_token().kind() == TokenKind::RParen)
         {
-            ctx.throw_custom(
+            return ctx.throw_custom(
                 "Unexpected token while parsing arguments variable, It needs to be either ',' or ')'.",
             );
         }
 
         if let Token::Comma(_) = ctx.get_curr_token() {
-            ctx.eat(TokenKind::Comma);
+            ctx.eat(TokenKind::Comma)?;
         }
     }
 
-    args
+    Ok(args)
 }
 
-fn parse_statements(ctx: &mut ParsingContext) -> Vec<ast::FnStmt> {
+pub fn parse_statements(ctx: &mut ParsingContext) -> ParseResult<Vec<ast::FnStmt>> {
     let mut body = vec![];
 
     while ctx.is_not_eof() {
-        ctx.eat_all_newlines();
+        ctx.eat_all_newlines()?;
 
-        if let Token::RBrace(_) = ctx.get_curr_token() {
+        if let Token::RBrace(_) | Token::Eof(_) = ctx.get_curr_token() {
             break;
         }
 
-        body.push(parse_statement(ctx));
+        let fn_stmt = ctx
+            .parse_or_recover(parse_statement)
+            .unwrap_or_else(ast::FnStmt::Error);
+
+        body.push(fn_stmt);
     }
 
-    body
+    Ok(body)
 }
 
-fn parse_statement(ctx: &mut ParsingContext) -> ast::FnStmt {
+fn parse_statement(ctx: &mut ParsingContext) -> ParseResult<ast::FnStmt> {
     let fn_stmt = match ctx.get_curr_token().kind() {
-        TokenKind::Let => ast::FnStmt::Let(statement_let::parse(ctx)),
+        TokenKind::Let => ast::FnStmt::Let(statement_let::parse(ctx)?),
+        TokenKind::Id if statement_match::is_match(ctx)? => {
+            ast::FnStmt::Match(statement_match::parse(ctx)?)
+        }
         TokenKind::Id => {
-            let id = identifier::parse(ctx);
+            let id = identifier::parse_name(ctx)?;
 
-            if ctx.peek_ahead_ignoring_newlines().kind() == TokenKind::Dot {
-                ctx.eat_all_newlines();
+            if ctx.peek_ahead_ignoring_newlines()?.kind() == TokenKind::Dot {
+                ctx.eat_all_newlines()?;
             }
 
             match ctx.get_curr_token().kind() {
-                TokenKind::LBrace => ast::FnStmt::FnCall(function_call::parse(ctx, id)),
-                TokenKind::Dot => {
-                    ast::FnStmt::MemberAccess(member_access::parse(ctx, ast::Expr::Id(id)))
+                TokenKind::LParen => {
+                    let fn_call = function_call::parse(ctx, id)?;
+
+                    if ctx.get_curr_token().kind() == TokenKind::NewLine
+                        && ctx.peek_ahead_ignoring_newlines()?.kind() == TokenKind::Dot
+                    {
+                        ctx.eat_all_newlines()?;
+                    }
+
+                    match ctx.get_curr_token().kind() {
+                        TokenKind::Dot => member_access_statement(ctx, ast::Expr::FnCall(fn_call))?,
+                        _ if expression::operator_ahead(ctx)?.is_some() => {
+                            ast::FnStmt::Expr(expression::parse_rest(ctx, fn_call.into())?)
+                        }
+                        _ => ast::FnStmt::FnCall(fn_call),
+                    }
                 }
-                _ => {
-                    ctx.throw_unexpected_token();
-                    panic!()
+                TokenKind::Dot => member_access_statement(ctx, ast::Expr::Id(id))?,
+                TokenKind::LBrace => ast::FnStmt::Expr(record::parse(ctx, id)?.into()),
+                _ if expression::operator_ahead(ctx)?.is_some() => {
+                    ast::FnStmt::Expr(expression::parse_rest(ctx, id.into())?)
                 }
+                TokenKind::NewLine | TokenKind::RBrace => ast::FnStmt::Expr(ast::Expr::Id(id)),
+                _ => return ctx.throw_unexpected_token(),
             }
         }
-        TokenKind::If => ast::FnStmt::If(statement_if::parse(ctx)),
-        TokenKind::Return => ast::FnStmt::Ret(statement_return::parse(ctx)),
-        TokenKind::Js => ast::FnStmt::LitJs(literal_js::parse(ctx)),
+        TokenKind::If => ast::FnStmt::If(statement_if::parse(ctx)?),
+        TokenKind::Return => ast::FnStmt::Ret(statement_return::parse(ctx)?),
+        TokenKind::Js => ast::FnStmt::LitJs(literal_js::parse(ctx)?),
+        TokenKind::Str
+        | TokenKind::Numeric
+        | TokenKind::Boolean
+        | TokenKind::JsxOpen
+        | TokenKind::LSqrBr
+        | TokenKind::LParen
+        | TokenKind::Dot
+        | TokenKind::Minus
+        | TokenKind::Bang => ast::FnStmt::Expr(expression::parse(ctx)?),
         TokenKind::NewLine => ast::FnStmt::Noop,
-        _ => {
-            ctx.throw_unexpected_token();
-            panic!()
-        }
+        _ => return ctx.throw_unexpected_token(),
     };
 
-    fn_stmt
+    Ok(fn_stmt)
+}
+
+/// A chain starting with `obj`, or the first operand of an expression when an
+/// operator follows the chain.
+fn member_access_statement(ctx: &mut ParsingContext, obj: ast::Expr) -> ParseResult<ast::FnStmt> {
+    let member_access = member_access::parse(ctx, obj)?;
+
+    if expression::operator_ahead(ctx)?.is_some() {
+        Ok(ast::FnStmt::Expr(expression::parse_rest(
+            ctx,
+            member_access.into(),
+        )?))
+    } else {
+        Ok(ast::FnStmt::MemberAccess(member_access))
+    }
 }
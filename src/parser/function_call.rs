@@ -4,29 +4,32 @@ use crate::{
     token::{Token, TokenKind},
 };
 
-use super::{context::ParsingContext, expression};
+use super::{
+    context::{ParseResult, ParsingContext},
+    expression,
+};
 
-pub fn parse(ctx: &mut ParsingContext, id: Ident) -> ast::FnCall {
+pub fn parse(ctx: &mut ParsingContext, id: Ident) -> ParseResult<ast::FnCall> {
     let span_from = id.span().from;
 
-    ctx.eat(TokenKind::LParen);
+    ctx.eat(TokenKind::LParen)?;
 
-    let args = parse_args(ctx);
+    let args = parse_args(ctx)?;
 
-    ctx.eat_all_newlines();
+    ctx.eat_all_newlines()?;
 
     let span_to = ctx.get_curr_token().span().to;
 
-    ctx.eat(TokenKind::RParen);
+    ctx.eat(TokenKind::RParen)?;
 
-    ast::FnCall {
+    Ok(ast::FnCall {
         id,
         args,
         span: Span::new(span_from, span_to),
-    }
+    })
 }
 
-fn parse_args(ctx: &mut ParsingContext) -> Vec<ast::Expr> {
+fn parse_args(ctx: &mut ParsingContext) -> ParseResult<Vec<ast::Expr>> {
     let mut args = vec![];
 
     while ctx.is_not_eof() {
@@ -34,16 +37,16 @@ fn parse_args(ctx: &mut ParsingContext) -> Vec<ast::Expr> {
             break;
         }
 
-        ctx.eat_all_newlines();
+        ctx.eat_all_newlines()?;
 
-        args.push(expression::parse(ctx));
+        args.push(expression::parse(ctx)?);
 
         if let Token::Comma(_) = ctx.get_curr_token() {
-            ctx.eat(TokenKind::Comma);
+            ctx.eat(TokenKind::Comma)?;
         }
 
-        ctx.eat_all_newlines();
+        ctx.eat_all_newlines()?;
     }
 
-    args
+    Ok(args)
 }
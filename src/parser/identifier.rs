@@ -3,60 +3,74 @@ use crate::{
     token::{Token, TokenKind},
 };
 
-use super::context::ParsingContext;
+use super::context::{ParseResult, ParsingContext};
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::Ident {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Ident> {
+    let id = parse_name(ctx)?;
+    let generics = parse_generics(ctx)?;
+
+    Ok(ast::Ident { generics, ..id })
+}
+
+/// Parses an identifier used as a value, where a `<` following it is a
+/// comparison rather than the start of its generics.
+pub fn parse_name(ctx: &mut ParsingContext) -> ParseResult<ast::Ident> {
     let current_token = ctx.get_curr_token();
 
     match current_token.value().as_str() {
-        "type" => {
-            ctx.throw_reserved_keywords("type");
-        }
-        "fn" => {
-            ctx.throw_reserved_keywords("fn");
-        }
+        "type" => return ctx.throw_reserved_keywords("type"),
+        "fn" => return ctx.throw_reserved_keywords("fn"),
         _ => {}
     }
 
     let span = current_token.span().clone();
     let string = current_token.value();
 
-    ctx.eat_without_consuming_jsx(TokenKind::Id);
+    ctx.eat_without_consuming_jsx(TokenKind::Id)?;
 
-    let generics = parse_generics(ctx);
-
-    let identifier = ast::Ident {
+    Ok(ast::Ident {
         string,
-        generics,
+        generics: vec![],
         span,
-    };
+    })
+}
+
+/// Parses a type annotation, its name can be a path like `App.Error`.
+pub fn parse_type(ctx: &mut ParsingContext) -> ParseResult<ast::Type> {
+    let mut id = parse(ctx)?;
+
+    while ctx.get_curr_token().kind() == TokenKind::Dot {
+        ctx.eat(TokenKind::Dot)?;
+        let id_part = parse(ctx)?;
+        id = id.concat(id_part);
+    }
 
-    identifier
+    Ok(ast::Type::Ident(id))
 }
 
-fn parse_generics(ctx: &mut ParsingContext) -> Vec<ast::Ident> {
+fn parse_generics(ctx: &mut ParsingContext) -> ParseResult<Vec<ast::Ident>> {
     let mut generics = vec![];
 
     if let Token::Lt(_) = ctx.get_curr_token() {
-        ctx.eat(TokenKind::Lt);
+        ctx.eat(TokenKind::Lt)?;
 
         while ctx.is_not_eof() {
             if let Token::Gt(_) = ctx.get_curr_token() {
-                ctx.eat(TokenKind::Gt);
+                ctx.eat(TokenKind::Gt)?;
                 break;
             }
 
-            generics.push(parse(ctx));
+            generics.push(parse(ctx)?);
 
             if let Token::Comma(_) = ctx.get_curr_token() {
-                ctx.eat(TokenKind::Comma);
+                ctx.eat(TokenKind::Comma)?;
             }
 
             if let Token::DblColon(_) = ctx.get_curr_token() {
-                ctx.throw_unexpected_token()
+                return ctx.throw_unexpected_token();
             }
         }
     }
 
-    generics
+    Ok(generics)
 }
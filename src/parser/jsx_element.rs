@@ -4,65 +4,70 @@ use crate::{
     token::{Token, TokenKind},
 };
 
-use super::{context::ParsingContext, expression};
+use super::{
+    context::{ParseResult, ParsingContext},
+    expression,
+};
 
-pub fn parse(ctx: &mut ParsingContext, caller_is_jsx: bool) -> ast::JsxElement {
+pub fn parse(ctx: &mut ParsingContext, caller_is_jsx: bool) -> ParseResult<ast::JsxElement> {
     if let Token::JsxOpen(name, span) = ctx.get_curr_token() {
-        let name = name;
         let span_from = span.from;
-        ctx.eat(TokenKind::JsxOpen);
+        ctx.eat(TokenKind::JsxOpen)?;
 
-        let attrs = parse_attrs(ctx);
+        let attrs = parse_attrs(ctx)?;
         let mut children = vec![];
         let mut self_closing = false;
 
         match ctx.get_curr_token() {
             Token::Gt(_) => {
-                ctx.eat_jsx(TokenKind::Gt);
-                children.append(&mut parse_children(ctx));
+                ctx.eat_jsx(TokenKind::Gt)?;
+                children.append(&mut parse_children(ctx)?);
 
                 if let Token::JsxClose(closed_name, _) = ctx.get_curr_token() {
                     if closed_name == name {
                         if caller_is_jsx {
-                            ctx.eat_jsx(TokenKind::JsxClose);
+                            ctx.eat_jsx(TokenKind::JsxClose)?;
                         } else {
-                            ctx.eat(TokenKind::JsxClose);
+                            ctx.eat(TokenKind::JsxClose)?;
                         }
                     } else {
-                        ctx.throw_custom("Unexpected closing tag.");
+                        let message = format!(
+                            "Unexpected closing tag </{closed_name}>, was expecting </{name}>."
+                        );
+                        return Err(ctx.error(&message).with_note(
+                            &format!("The element <{name}> is opened here."),
+                            Some(span),
+                        ));
                     }
                 }
             }
             Token::JsxSelfClose(_) => {
                 self_closing = true;
                 if caller_is_jsx {
-                    ctx.eat_jsx(TokenKind::JsxSelfClose);
+                    ctx.eat_jsx(TokenKind::JsxSelfClose)?;
                 } else {
-                    ctx.eat(TokenKind::JsxSelfClose);
+                    ctx.eat(TokenKind::JsxSelfClose)?;
                 }
             }
-            _ => {
-                ctx.throw_unexpected_token();
-            }
+            _ => return ctx.throw_unexpected_token(),
         }
 
         let span_to = ctx.get_curr_token().span().from;
         let span = Span::new(span_from, span_to);
 
-        ast::JsxElement {
+        Ok(ast::JsxElement {
             name,
             attrs,
             children,
             self_closing,
             span,
-        }
+        })
     } else {
-        ctx.throw_unexpected_token_with_expecting(&TokenKind::JsxOpen);
-        panic!()
+        ctx.throw_unexpected_token_with_expecting(&TokenKind::JsxOpen)
     }
 }
 
-fn parse_attrs(ctx: &mut ParsingContext) -> Vec<ast::JsxElementAttribute> {
+fn parse_attrs(ctx: &mut ParsingContext) -> ParseResult<Vec<ast::JsxElementAttribute>> {
     let mut attrs = vec![];
 
     while ctx.is_not_eof() {
@@ -78,34 +83,41 @@ fn parse_attrs(ctx: &mut ParsingContext) -> Vec<ast::JsxElementAttribute> {
 
         let name = ctx.get_curr_token().value();
 
-        ctx.eat(TokenKind::Id);
-        ctx.eat(TokenKind::Eq);
+        ctx.eat(TokenKind::Id)?;
+        ctx.eat(TokenKind::Eq)?;
 
         let mut expecting_rbrace = false;
 
         if ctx.get_curr_token().kind() == TokenKind::LBrace {
-            ctx.eat(TokenKind::LBrace);
+            ctx.eat(TokenKind::LBrace)?;
             expecting_rbrace = true;
         }
 
-        let expr = expression::parse(ctx);
+        // Without braces the `>` closing the tag would read as a comparison.
+        let expr = if expecting_rbrace {
+            expression::parse(ctx)?
+        } else {
+            let expr = expression::parse_operand(ctx)?;
+            ctx.eat_all_newlines()?;
+            expr
+        };
         let span_to = ctx.get_prev_token().span().from;
 
         attrs.push(ast::JsxElementAttribute {
             name,
-            expr: expr,
+            expr,
             span: Span::new(span_from, span_to),
         });
 
         if expecting_rbrace {
-            ctx.eat(TokenKind::RBrace);
+            ctx.eat(TokenKind::RBrace)?;
         }
     }
 
-    attrs
+    Ok(attrs)
 }
 
-fn parse_children(ctx: &mut ParsingContext) -> Vec<ast::Expr> {
+fn parse_children(ctx: &mut ParsingContext) -> ParseResult<Vec<ast::Expr>> {
     let mut children = vec![];
 
     while ctx.is_not_eof() {
@@ -113,22 +125,22 @@ fn parse_children(ctx: &mut ParsingContext) -> Vec<ast::Expr> {
             break;
         }
 
-        ctx.eat_all_newlines_jsx();
+        ctx.eat_all_newlines_jsx()?;
 
         if ctx.get_curr_token().kind() == TokenKind::LBrace {
-            ctx.eat(TokenKind::LBrace);
-            children.push(expression::parse(ctx));
-            ctx.eat(TokenKind::RBrace);
+            ctx.eat(TokenKind::LBrace)?;
+            children.push(expression::parse(ctx)?);
+            ctx.eat(TokenKind::RBrace)?;
         } else {
-            children.push(expression::parse(ctx));
+            children.push(expression::parse(ctx)?);
         }
 
         if let TokenKind::JsxOpen = TokenKind::from(ctx.get_curr_token()) {
-            children.push(ast::Expr::JsxElement(parse(ctx, true)));
+            children.push(ast::Expr::JsxElement(parse(ctx, true)?));
         }
 
-        ctx.eat_all_newlines_jsx();
+        ctx.eat_all_newlines_jsx()?;
     }
 
-    children
+    Ok(children)
 }
@@ -1,11 +1,11 @@
 use crate::{ast, token::TokenKind};
 
-use super::context::ParsingContext;
+use super::context::{ParseResult, ParsingContext};
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::LitBoolean {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::LitBoolean> {
     let raw = ctx.get_curr_token().value();
-    let value = ctx.get_curr_token().value().parse::<bool>().unwrap();
+    let value = raw == "true";
     let span = ctx.get_curr_token().span();
-    ctx.eat(TokenKind::Boolean);
-    ast::LitBoolean { raw, value, span }
+    ctx.eat(TokenKind::Boolean)?;
+    Ok(ast::LitBoolean { raw, value, span })
 }
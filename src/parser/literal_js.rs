@@ -1,16 +1,16 @@
 use crate::{ast, token::TokenKind};
 
-use super::context::ParsingContext;
+use super::context::{ParseResult, ParsingContext};
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::LitJs {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::LitJs> {
     let current_token = ctx.get_curr_token();
     let value = current_token.value();
     let span = current_token.span().clone();
 
-    ctx.eat(TokenKind::Js);
-    ast::LitJs {
+    ctx.eat(TokenKind::Js)?;
+    Ok(ast::LitJs {
         raw: format!("\"{value}\""),
         value,
         span,
-    }
+    })
 }
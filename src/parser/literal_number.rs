@@ -1,11 +1,14 @@
 use crate::{ast, token::TokenKind};
 
-use super::context::ParsingContext;
+use super::context::{ParseResult, ParsingContext};
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::LitNumber {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::LitNumber> {
     let raw = ctx.get_curr_token().value();
-    let value = ctx.get_curr_token().value().parse::<f64>().unwrap();
+    let value = match raw.parse::<f64>() {
+        Ok(value) => value,
+        Err(_) => return ctx.throw_custom(&format!("Invalid number literal {raw:?}.")),
+    };
     let span = ctx.get_curr_token().span();
-    ctx.eat(TokenKind::Numeric);
-    ast::LitNumber { raw, value, span }
+    ctx.eat(TokenKind::Numeric)?;
+    Ok(ast::LitNumber { raw, value, span })
 }
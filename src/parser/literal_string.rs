@@ -1,11 +1,76 @@
-use crate::{ast, token::TokenKind};
+use crate::{ast, diagnostic::Diagnostic, lexer, span::Span, token::TokenKind};
 
-use super::context::ParsingContext;
+use super::{
+    context::{ParseResult, ParsingContext},
+    expression,
+};
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::LitString {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::LitString> {
     let raw = format!("\"{}\"", ctx.get_curr_token().value());
     let value = ctx.get_curr_token().value();
     let span = ctx.get_curr_token().span();
-    ctx.eat(TokenKind::Str);
-    ast::LitString { raw, value, span }
+    ctx.eat(TokenKind::Str)?;
+
+    // The value ends right before the closing quote.
+    let parts = parse_parts(ctx, &value, span.to.saturating_sub(value.len() + 1))?;
+
+    Ok(ast::LitString {
+        raw,
+        value,
+        parts,
+        span,
+    })
+}
+
+/// Splits `value` around its `#{...}` interpolations, `offset` being where
+/// `value` starts in the file.
+fn parse_parts(
+    ctx: &mut ParsingContext,
+    value: &str,
+    offset: usize,
+) -> ParseResult<Vec<ast::StrPart>> {
+    let bytes = value.as_bytes();
+    let mut parts = vec![];
+    let mut text_from = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'#' if bytes.get(i + 1) == Some(&b'{') => {
+                if text_from < i {
+                    parts.push(ast::StrPart::Text(value[text_from..i].to_string()));
+                }
+
+                let expr_from = i + 2;
+                let len = match lexer::interpolation_len(&value[expr_from..]) {
+                    Some(len) => len,
+                    None => return ctx.throw_custom("The interpolation is never closed."),
+                };
+                let expr_to = expr_from + len - 1;
+
+                if value[expr_from..expr_to].trim().is_empty() {
+                    return Err(Diagnostic {
+                        span: Span::new(offset + expr_to, offset + expr_to + 1),
+                        ..ctx.error("The interpolation is empty, write the value to insert between `#{` and `}`.")
+                    });
+                }
+
+                let expr =
+                    ctx.parse_slice(offset + expr_from, offset + expr_to, expression::parse)?;
+
+                parts.push(ast::StrPart::Expr(expr));
+
+                i = expr_to + 1;
+                text_from = i;
+            }
+            _ => i += 1,
+        }
+    }
+
+    if text_from < bytes.len() {
+        parts.push(ast::StrPart::Text(value[text_from..].to_string()));
+    }
+
+    Ok(parts)
 }
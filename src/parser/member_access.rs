@@ -4,21 +4,26 @@ use crate::{
     token::TokenKind,
 };
 
-use super::{context::ParsingContext, expression};
+use super::{
+    context::{ParseResult, ParsingContext},
+    expression,
+};
+
+pub fn parse(ctx: &mut ParsingContext, obj: ast::Expr) -> ParseResult<ast::MemberAccess> {
+    ctx.eat(TokenKind::Dot)?;
 
-pub fn parse(ctx: &mut ParsingContext, obj: ast::Expr) -> ast::MemberAccess {
-    ctx.eat(TokenKind::Dot);
+    if ctx.get_curr_token().kind() == TokenKind::Id && ctx.get_curr_token().value() == "await" {
+        ctx.awaits = true;
+    }
 
-    let prop = expression::parse(ctx);
+    let prop = expression::parse_primary(ctx)?;
 
     let span_from = obj.span().from;
     let span_to = prop.span().to;
 
-    let expr = ast::MemberAccess {
+    Ok(ast::MemberAccess {
         obj,
         prop,
         span: Span::new(span_from, span_to),
-    };
-
-    expr
+    })
 }
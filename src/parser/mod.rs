@@ -1,28 +1,50 @@
+mod array;
 mod context;
 mod expression;
 mod function;
 mod function_call;
 mod identifier;
-mod statement_if;
-mod literal_js;
 mod jsx_element;
-mod statement_let;
-mod member_access;
+mod literal_boolean;
+mod literal_js;
 mod literal_number;
-mod statement_return;
+mod literal_string;
+mod member_access;
+mod record;
 mod root;
 mod statement;
-mod literal_string;
-mod array;
+mod statement_enum;
+mod statement_extern;
+mod statement_if;
+mod statement_import;
+mod statement_let;
+mod statement_match;
 mod statement_module;
+mod statement_record;
+mod statement_return;
+mod statement_use;
 mod statements;
-mod literal_boolean;
+mod variant;
 
 use self::context::ParsingContext;
-use crate::{ast, lexer::Lexer};
+use crate::{ast, diagnostic::Diagnostic, lexer::Lexer};
+
+/// Parses a whole file, reporting every syntax error found along the way.
+pub fn parse(lexer: &mut Lexer) -> Result<ast::Root, Vec<Diagnostic>> {
+    let (root, errors) = parse_with_recovery(lexer);
 
-pub fn parse(lexer: &mut Lexer) -> ast::Root {
+    if errors.is_empty() {
+        Ok(root)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parses a whole file, returning the syntax tree even when it contains errors.
+/// Statements that failed to parse show up as `Error` placeholders.
+pub fn parse_with_recovery(lexer: &mut Lexer) -> (ast::Root, Vec<Diagnostic>) {
     let mut context = ParsingContext::new(lexer);
-    let ast = root::parse(&mut context);
-    ast
+    let root = root::parse(&mut context);
+
+    (root, context.errors)
 }
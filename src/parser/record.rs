@@ -0,0 +1,58 @@
+use crate::{ast, span::Span, token::TokenKind};
+
+use super::{
+    context::{ParseResult, ParsingContext},
+    expression, identifier,
+};
+
+pub fn parse(ctx: &mut ParsingContext, id: ast::Ident) -> ParseResult<ast::InitRecord> {
+    let span_from = id.span.from;
+
+    ctx.eat(TokenKind::LBrace)?;
+    ctx.eat_all_newlines()?;
+
+    let mut props = vec![];
+
+    while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RBrace {
+        props.push(parse_prop(ctx)?);
+
+        ctx.eat_all_newlines()?;
+
+        if ctx.get_curr_token().kind() == TokenKind::Comma {
+            ctx.eat(TokenKind::Comma)?;
+            ctx.eat_all_newlines()?;
+        } else if ctx.get_curr_token().kind() != TokenKind::RBrace {
+            return ctx.throw_unexpected_token_with_expecting(&TokenKind::RBrace);
+        }
+    }
+
+    ctx.eat(TokenKind::RBrace)?;
+
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::InitRecord {
+        id,
+        props,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+fn parse_prop(ctx: &mut ParsingContext) -> ParseResult<ast::InitObjectProp> {
+    if ctx.get_curr_token().kind() == TokenKind::Dot {
+        ctx.eat(TokenKind::Dot)?;
+        ctx.eat(TokenKind::Dot)?;
+        ctx.eat(TokenKind::Dot)?;
+
+        return Ok(ast::InitObjectProp::Spread(identifier::parse(ctx)?));
+    }
+
+    let id = identifier::parse(ctx)?;
+
+    if ctx.get_curr_token().kind() == TokenKind::DblColon {
+        ctx.eat(TokenKind::DblColon)?;
+
+        Ok(ast::InitObjectProp::KeyValue(id, expression::parse(ctx)?))
+    } else {
+        Ok(ast::InitObjectProp::Key(id))
+    }
+}
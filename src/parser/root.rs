@@ -6,13 +6,18 @@ pub fn parse(ctx: &mut ParsingContext) -> ast::Root {
     let mut stmts = vec![];
 
     while ctx.is_not_eof() {
-        stmts.push(statement::parse(ctx));
+        let stmt = ctx
+            .parse_or_recover(statement::parse)
+            .unwrap_or_else(ast::Stmt::Error);
+
+        stmts.push(stmt);
     }
 
     ast::Root {
         name: ctx.lexer.filepath.clone(),
         path: ctx.lexer.filepath.clone(),
         stmts,
+        comments: ctx.lexer.comments.clone(),
         span: Span::new(0, ctx.lexer.len),
     }
 }
@@ -1,52 +1,132 @@
-use crate::{ast, token::TokenKind};
+use crate::{ast, span::Span, token::TokenKind};
 
 use super::{
-    context::ParsingContext, function, function_call, identifier, literal_js, member_access,
-    statement_let, statement_module, statement_return,
+    context::{ParseResult, ParsingContext},
+    function, function_call, identifier, literal_js, member_access, statement_enum,
+    statement_extern, statement_if, statement_import, statement_let, statement_match,
+    statement_module, statement_record, statement_return, statement_use,
 };
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::Stmt {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Stmt> {
     let token = ctx.get_curr_token();
 
     let statement: ast::Stmt = match TokenKind::from(&token) {
-        TokenKind::Mod => ast::Stmt::Mod(statement_module::parse(ctx)),
-        TokenKind::Fn => ast::Stmt::Fn(function::parse(ctx, false)),
-        TokenKind::Let => ast::Stmt::Let(statement_let::parse(ctx)),
-        TokenKind::Return => ast::Stmt::Return(statement_return::parse(ctx)),
-        TokenKind::Js => ast::Stmt::LitJs(literal_js::parse(ctx)),
+        TokenKind::Mod => ast::Stmt::Mod(statement_module::parse(ctx)?),
+        TokenKind::Fn => ast::Stmt::Fn(function::parse(ctx, false)?),
+        TokenKind::Let => ast::Stmt::Let(statement_let::parse(ctx)?),
+        TokenKind::Return => ast::Stmt::Return(statement_return::parse(ctx)?),
+        TokenKind::If => ast::Stmt::If(statement_if::parse(ctx)?),
+        TokenKind::Js => ast::Stmt::LitJs(literal_js::parse(ctx)?),
+        TokenKind::Id if statement_match::is_match(ctx)? => {
+            ast::Stmt::Match(statement_match::parse(ctx)?)
+        }
+        // `use`, `record`, `enum`, `extern`, `import` and `pub` are only keywords
+        // at the start of a statement, `fn use(...)` and `app.use(...)` keep
+        // working.
+        TokenKind::Id
+            if token.value() == "pub"
+                && matches!(
+                    ctx.peek_ahead()?.kind(),
+                    TokenKind::Fn | TokenKind::Let | TokenKind::Mod | TokenKind::Id
+                ) =>
+        {
+            parse_public(ctx)?
+        }
+        TokenKind::Id if token.value() == "record" && ctx.peek_ahead()?.kind() == TokenKind::Id => {
+            ast::Stmt::Record(statement_record::parse(ctx)?)
+        }
+        TokenKind::Id if token.value() == "enum" && ctx.peek_ahead()?.kind() == TokenKind::Id => {
+            ast::Stmt::Enum(statement_enum::parse(ctx)?)
+        }
+        TokenKind::Id
+            if token.value() == "extern"
+                && matches!(ctx.peek_ahead()?.kind(), TokenKind::Fn | TokenKind::Mod) =>
+        {
+            statement_extern::parse(ctx)?
+        }
+        TokenKind::Id
+            if token.value() == "import"
+                && matches!(
+                    ctx.peek_ahead()?.kind(),
+                    TokenKind::Id | TokenKind::Str | TokenKind::LBrace | TokenKind::Multiply
+                ) =>
+        {
+            ast::Stmt::Import(statement_import::parse(ctx)?)
+        }
+        TokenKind::Id
+            if token.value() == "use"
+                && matches!(ctx.peek_ahead()?.kind(), TokenKind::Id | TokenKind::Str) =>
+        {
+            ast::Stmt::Use(statement_use::parse(ctx)?)
+        }
         TokenKind::Id => {
-            let id = identifier::parse(ctx);
+            let id = identifier::parse(ctx)?;
 
-            if ctx.peek_ahead_ignoring_newlines().kind() == TokenKind::Dot {
-                ctx.eat_all_newlines();
+            if ctx.peek_ahead_ignoring_newlines()?.kind() == TokenKind::Dot {
+                ctx.eat_all_newlines()?;
             }
 
             let mut expr: ast::Expr = match ctx.get_curr_token().kind() {
-                TokenKind::LParen => function_call::parse(ctx, id).into(),
-                TokenKind::Dot => member_access::parse(ctx, ast::Expr::Id(id)).into(),
-
-                _ => {
-                    ctx.throw_unexpected_token();
-                    panic!();
-                }
+                TokenKind::LParen => function_call::parse(ctx, id)?.into(),
+                TokenKind::Dot => member_access::parse(ctx, ast::Expr::Id(id))?.into(),
+                _ => return ctx.throw_unexpected_token(),
             };
 
-            if ctx.peek_ahead_ignoring_newlines().kind() == TokenKind::Dot {
-                ctx.eat_all_newlines();
-                expr = member_access::parse(ctx, expr).into();
+            if ctx.peek_ahead_ignoring_newlines()?.kind() == TokenKind::Dot {
+                ctx.eat_all_newlines()?;
+                expr = member_access::parse(ctx, expr)?.into();
             }
 
             expr.into()
         }
         TokenKind::NewLine => {
-            ctx.eat(TokenKind::NewLine);
+            ctx.eat(TokenKind::NewLine)?;
             ast::Stmt::Noop
         }
-        _ => {
-            ctx.throw_unexpected_token();
-            panic!()
+        _ => return ctx.throw_unexpected_token(),
+    };
+
+    Ok(statement)
+}
+
+/// `pub fn`, `pub let` and `pub enum`, the definitions other files can use.
+fn parse_public(ctx: &mut ParsingContext) -> ParseResult<ast::Stmt> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Id)?;
+
+    let token = ctx.get_curr_token();
+
+    let stmt = match token.kind() {
+        TokenKind::Fn => {
+            let v = function::parse(ctx, false)?;
+
+            ast::Stmt::Fn(ast::Fn {
+                public: true,
+                span: Span::new(span_from, v.span.to),
+                ..v
+            })
+        }
+        TokenKind::Let => {
+            let v = statement_let::parse(ctx)?;
+
+            ast::Stmt::Let(ast::Let {
+                public: true,
+                span: Span::new(span_from, v.span.to),
+                ..v
+            })
+        }
+        TokenKind::Id if token.value() == "enum" => {
+            let v = statement_enum::parse(ctx)?;
+
+            ast::Stmt::Enum(ast::Enum {
+                public: true,
+                span: Span::new(span_from, v.span.to),
+                ..v
+            })
         }
+        _ => return ctx.throw_custom("Only fns, lets and enums can be declared pub."),
     };
 
-    statement
+    Ok(stmt)
 }
@@ -0,0 +1,73 @@
+use crate::{ast, span::Span, token::TokenKind};
+
+use super::{
+    context::{ParseResult, ParsingContext},
+    identifier,
+};
+
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Enum> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Id)?;
+
+    let id = identifier::parse(ctx)?;
+
+    ctx.eat(TokenKind::LBrace)?;
+    ctx.eat_all_newlines()?;
+
+    let mut variants = vec![];
+
+    while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RBrace {
+        variants.push(parse_variant(ctx)?);
+
+        ctx.eat_all_newlines()?;
+
+        if ctx.get_curr_token().kind() == TokenKind::Comma {
+            ctx.eat(TokenKind::Comma)?;
+            ctx.eat_all_newlines()?;
+        } else if ctx.get_curr_token().kind() != TokenKind::RBrace {
+            return ctx.throw_unexpected_token_with_expecting(&TokenKind::RBrace);
+        }
+    }
+
+    let span_to = ctx.get_curr_token().span().to;
+
+    ctx.eat(TokenKind::RBrace)?;
+
+    Ok(ast::Enum {
+        public: false,
+        id,
+        variants,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+fn parse_variant(ctx: &mut ParsingContext) -> ParseResult<ast::EnumVariant> {
+    let span_from = ctx.get_curr_token().span().from;
+    let id = identifier::parse(ctx)?;
+    let mut fields = vec![];
+
+    if ctx.get_curr_token().kind() == TokenKind::LParen {
+        ctx.eat(TokenKind::LParen)?;
+
+        while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RParen {
+            fields.push(identifier::parse_type(ctx)?);
+
+            if ctx.get_curr_token().kind() == TokenKind::Comma {
+                ctx.eat(TokenKind::Comma)?;
+            } else if ctx.get_curr_token().kind() != TokenKind::RParen {
+                return ctx.throw_unexpected_token_with_expecting(&TokenKind::RParen);
+            }
+        }
+
+        ctx.eat(TokenKind::RParen)?;
+    }
+
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::EnumVariant {
+        id,
+        fields,
+        span: Span::new(span_from, span_to),
+    })
+}
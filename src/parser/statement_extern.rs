@@ -0,0 +1,69 @@
+use crate::{ast, span::Span, token::TokenKind};
+
+use super::{
+    context::{ParseResult, ParsingContext},
+    function, identifier, statement_module,
+};
+
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Stmt> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Id)?;
+
+    if ctx.get_curr_token().kind() == TokenKind::Fn {
+        return Ok(ast::Stmt::Fn(parse_fn(ctx, span_from)?));
+    }
+
+    ctx.eat(TokenKind::Mod)?;
+
+    let path = statement_module::parse_path(ctx)?;
+
+    ctx.eat(TokenKind::LBrace)?;
+    ctx.eat_all_newlines()?;
+
+    let mut stmts = vec![];
+
+    while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RBrace {
+        if ctx.get_curr_token().kind() != TokenKind::Fn {
+            return ctx.throw_custom("Only fns can be declared in an extern mod.");
+        }
+
+        let span_from = ctx.get_curr_token().span().from;
+
+        stmts.push(ast::Stmt::Fn(parse_fn(ctx, span_from)?));
+
+        ctx.eat_all_newlines()?;
+    }
+
+    let span_to = ctx.get_curr_token().span().to;
+
+    ctx.eat(TokenKind::RBrace)?;
+
+    Ok(ast::Stmt::Mod(ast::Mod {
+        path,
+        external: true,
+        stmts,
+        span: Span::new(span_from, span_to),
+    }))
+}
+
+/// Parses the signature of an extern fn, there is no body to parse.
+fn parse_fn(ctx: &mut ParsingContext, span_from: usize) -> ParseResult<ast::Fn> {
+    ctx.eat(TokenKind::Fn)?;
+
+    let id = identifier::parse(ctx)?;
+    let (args, output) = function::parse_signature(ctx)?;
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::Fn {
+        args,
+        id,
+        anonymous: false,
+        external: true,
+        public: false,
+        is_async: false,
+        stmts: vec![],
+        output,
+        span: Span::new(span_from, span_to),
+    })
+}
@@ -1,7 +1,72 @@
-use crate::ast;
+use crate::{
+    ast,
+    span::{Span, Spanned},
+    token::TokenKind,
+};
 
-use super::context::ParsingContext;
+use super::{
+    context::{ParseResult, ParsingContext},
+    expression, function,
+};
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::If {
-    todo!()
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::If> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::If)?;
+
+    let awaits = std::mem::replace(&mut ctx.awaits, false);
+    let test = ctx.without_braces(expression::parse)?;
+    let truthy = parse_block(ctx)?;
+
+    while ctx.get_curr_token().kind() == TokenKind::NewLine
+        && ctx.peek_ahead_ignoring_newlines()?.kind() == TokenKind::Else
+    {
+        ctx.eat_all_newlines()?;
+    }
+
+    let falsy = if ctx.get_curr_token().kind() == TokenKind::Else {
+        ctx.eat(TokenKind::Else)?;
+
+        if ctx.get_curr_token().kind() == TokenKind::If {
+            let else_if = parse(ctx)?;
+
+            Some(ast::Block {
+                span: else_if.span(),
+                stmts: vec![ast::FnStmt::If(else_if)],
+            })
+        } else {
+            Some(parse_block(ctx)?)
+        }
+    } else {
+        None
+    };
+
+    let span_to = ctx.get_prev_token().span().to;
+    let branches_await = ctx.awaits;
+
+    ctx.awaits |= awaits;
+
+    Ok(ast::If {
+        test,
+        truthy,
+        falsy,
+        awaits: branches_await,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+pub fn parse_block(ctx: &mut ParsingContext) -> ParseResult<ast::Block> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::LBrace)?;
+
+    let stmts = function::parse_statements(ctx)?;
+    let span_to = ctx.get_curr_token().span().to;
+
+    ctx.eat(TokenKind::RBrace)?;
+
+    Ok(ast::Block {
+        stmts,
+        span: Span::new(span_from, span_to),
+    })
 }
@@ -0,0 +1,153 @@
+use crate::{ast, span::Span, token::TokenKind};
+
+use super::{
+    context::{ParseResult, ParsingContext},
+    identifier,
+};
+
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Import> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Id)?;
+
+    let mut default = None;
+    let mut namespace = None;
+    let mut names = vec![];
+
+    // `import "./styles.css"` only runs the module.
+    if ctx.get_curr_token().kind() != TokenKind::Str {
+        if ctx.get_curr_token().kind() == TokenKind::Id {
+            default = Some(parse_name(ctx)?);
+        }
+
+        if default.is_none() || ctx.get_curr_token().kind() == TokenKind::Comma {
+            if default.is_some() {
+                ctx.eat(TokenKind::Comma)?;
+            }
+
+            match ctx.get_curr_token().kind() {
+                TokenKind::LBrace => names = parse_names(ctx)?,
+                TokenKind::Multiply => namespace = Some(parse_namespace(ctx)?),
+                _ => return ctx.throw_unexpected_token(),
+            }
+        }
+
+        eat_word(ctx, "from")?;
+    }
+
+    let source = ctx.get_curr_token().value();
+
+    ctx.eat(TokenKind::Str)?;
+
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::Import {
+        default,
+        namespace,
+        names,
+        source,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+fn parse_names(ctx: &mut ParsingContext) -> ParseResult<Vec<ast::ImportName>> {
+    ctx.eat(TokenKind::LBrace)?;
+    ctx.eat_all_newlines()?;
+
+    let mut names = vec![];
+
+    while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RBrace {
+        names.push(parse_specifier(ctx)?);
+
+        ctx.eat_all_newlines()?;
+
+        if ctx.get_curr_token().kind() == TokenKind::Comma {
+            ctx.eat(TokenKind::Comma)?;
+            ctx.eat_all_newlines()?;
+        } else if ctx.get_curr_token().kind() != TokenKind::RBrace {
+            return ctx.throw_unexpected_token_with_expecting(&TokenKind::RBrace);
+        }
+    }
+
+    ctx.eat(TokenKind::RBrace)?;
+
+    Ok(names)
+}
+
+/// `* as path`, the module as a whole.
+fn parse_namespace(ctx: &mut ParsingContext) -> ParseResult<ast::ImportName> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Multiply)?;
+    eat_word(ctx, "as")?;
+
+    let id = identifier::parse(ctx)?;
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::ImportName {
+        exported: None,
+        id,
+        ty: ast::Type::Default,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+/// `serve` or `serve: Fn<Handler, Unit>`.
+fn parse_name(ctx: &mut ParsingContext) -> ParseResult<ast::ImportName> {
+    let span_from = ctx.get_curr_token().span().from;
+    let id = identifier::parse(ctx)?;
+    let ty = parse_annotation(ctx)?;
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::ImportName {
+        exported: None,
+        id,
+        ty,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+/// A name between braces, which can be renamed, `serve as listen` or
+/// `serve as listen: Fn<Handler, Unit>`.
+fn parse_specifier(ctx: &mut ParsingContext) -> ParseResult<ast::ImportName> {
+    let span_from = ctx.get_curr_token().span().from;
+    let mut id = identifier::parse(ctx)?;
+    let mut exported = None;
+
+    if ctx.get_curr_token().kind() == TokenKind::Id && ctx.get_curr_token().value() == "as" {
+        ctx.eat(TokenKind::Id)?;
+        exported = Some(std::mem::replace(&mut id, identifier::parse(ctx)?));
+    }
+
+    let ty = parse_annotation(ctx)?;
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::ImportName {
+        exported,
+        id,
+        ty,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+fn parse_annotation(ctx: &mut ParsingContext) -> ParseResult<ast::Type> {
+    if ctx.get_curr_token().kind() == TokenKind::DblColon {
+        ctx.eat(TokenKind::DblColon)?;
+        identifier::parse_type(ctx)
+    } else {
+        Ok(ast::Type::Default)
+    }
+}
+
+fn eat_word(ctx: &mut ParsingContext, word: &str) -> ParseResult<()> {
+    let token = ctx.get_curr_token();
+
+    if token.kind() != TokenKind::Id || token.value() != word {
+        return ctx.throw_custom(&format!(
+            "Expected `{word}` but found \"{}\".",
+            token.value()
+        ));
+    }
+
+    ctx.eat(TokenKind::Id)
+}
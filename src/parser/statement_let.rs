@@ -2,27 +2,37 @@ use crate::ast;
 use crate::span::Span;
 use crate::token::TokenKind;
 
-use super::context::ParsingContext;
-use super::expression;
+use super::context::{ParseResult, ParsingContext};
+use super::{expression, identifier};
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::Let {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Let> {
     let span_from = ctx.get_curr_token().span().from;
 
-    ctx.eat(TokenKind::Let);
+    ctx.eat(TokenKind::Let)?;
 
     let id = ctx.get_curr_token().value();
 
-    ctx.eat(TokenKind::Id);
-    ctx.eat(TokenKind::Eq);
-    ctx.eat_all_newlines();
+    ctx.eat(TokenKind::Id)?;
 
-    let expr = expression::parse(ctx);
+    let ty = if ctx.get_curr_token().kind() == TokenKind::DblColon {
+        ctx.eat(TokenKind::DblColon)?;
+        identifier::parse_type(ctx)?
+    } else {
+        ast::Type::Default
+    };
+
+    ctx.eat(TokenKind::Eq)?;
+    ctx.eat_all_newlines()?;
+
+    let expr = expression::parse(ctx)?;
 
     let span_to = ctx.get_curr_token().span().from;
 
-    ast::Let {
+    Ok(ast::Let {
+        public: false,
         id,
+        ty,
         expr,
         span: Span::new(span_from, span_to),
-    }
+    })
 }
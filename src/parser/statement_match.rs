@@ -0,0 +1,160 @@
+use crate::{
+    ast,
+    span::{Span, Spanned},
+    token::TokenKind,
+};
+
+use super::{
+    context::{ParseResult, ParsingContext},
+    expression, identifier, literal_boolean, literal_number, literal_string, statement_if,
+};
+
+/// `match` is only a keyword when a value follows it, `match(...)` and
+/// `match.x` keep working.
+pub fn is_match(ctx: &mut ParsingContext) -> ParseResult<bool> {
+    Ok(ctx.get_curr_token().value() == "match"
+        && matches!(
+            ctx.peek_ahead()?.kind(),
+            TokenKind::Id
+                | TokenKind::Str
+                | TokenKind::Numeric
+                | TokenKind::Boolean
+                | TokenKind::Js
+                | TokenKind::LSqrBr
+                | TokenKind::Minus
+                | TokenKind::Bang
+        ))
+}
+
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Match> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Id)?;
+
+    let awaits = std::mem::replace(&mut ctx.awaits, false);
+    let expr = ctx.without_braces(expression::parse)?;
+
+    ctx.eat(TokenKind::LBrace)?;
+    ctx.eat_all_newlines()?;
+
+    let mut arms = vec![];
+
+    while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RBrace {
+        arms.push(parse_arm(ctx)?);
+
+        ctx.eat_all_newlines()?;
+
+        if ctx.get_curr_token().kind() == TokenKind::Comma {
+            ctx.eat(TokenKind::Comma)?;
+            ctx.eat_all_newlines()?;
+        }
+    }
+
+    let span_to = ctx.get_curr_token().span().to;
+
+    ctx.eat(TokenKind::RBrace)?;
+
+    let arms_await = ctx.awaits;
+
+    ctx.awaits |= awaits;
+
+    Ok(ast::Match {
+        expr,
+        arms,
+        awaits: arms_await,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+fn parse_arm(ctx: &mut ParsingContext) -> ParseResult<ast::MatchArm> {
+    let span_from = ctx.get_curr_token().span().from;
+    let pattern = parse_pattern(ctx)?;
+
+    ctx.eat(TokenKind::Arrow)?;
+    ctx.eat_all_newlines()?;
+
+    let body = if ctx.get_curr_token().kind() == TokenKind::LBrace {
+        statement_if::parse_block(ctx)?
+    } else {
+        let expr = ctx.without_line_chains(expression::parse)?;
+
+        ast::Block {
+            span: expr.span(),
+            stmts: vec![ast::FnStmt::Expr(expr)],
+        }
+    };
+
+    Ok(ast::MatchArm {
+        pattern,
+        span: Span::new(span_from, body.span.to),
+        body,
+    })
+}
+
+fn parse_pattern(ctx: &mut ParsingContext) -> ParseResult<ast::Pattern> {
+    let pattern = match ctx.get_curr_token().kind() {
+        TokenKind::Dot => ast::Pattern::Variant(parse_variant(ctx)?),
+        TokenKind::Str => ast::Pattern::Literal(literal_string::parse(ctx)?.into()),
+        TokenKind::Numeric => ast::Pattern::Literal(literal_number::parse(ctx)?.into()),
+        TokenKind::Minus => {
+            let span_from = ctx.get_curr_token().span().from;
+
+            ctx.eat(TokenKind::Minus)?;
+
+            let number = literal_number::parse(ctx)?;
+
+            ast::Pattern::Literal(
+                ast::LitNumber {
+                    raw: format!("-{}", number.raw),
+                    value: -number.value,
+                    span: Span::new(span_from, number.span.to),
+                }
+                .into(),
+            )
+        }
+        TokenKind::Boolean => ast::Pattern::Literal(literal_boolean::parse(ctx)?.into()),
+        TokenKind::Id if ctx.get_curr_token().value() == "_" => {
+            let span = ctx.get_curr_token().span();
+
+            ctx.eat(TokenKind::Id)?;
+            ast::Pattern::Wildcard(span)
+        }
+        TokenKind::Id => ast::Pattern::Binding(identifier::parse(ctx)?),
+        _ => return ctx.throw_unexpected_token(),
+    };
+
+    Ok(pattern)
+}
+
+fn parse_variant(ctx: &mut ParsingContext) -> ParseResult<ast::VariantPattern> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Dot)?;
+
+    let id = identifier::parse(ctx)?;
+    let mut args = vec![];
+
+    if ctx.get_curr_token().kind() == TokenKind::LParen {
+        ctx.eat(TokenKind::LParen)?;
+
+        while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RParen {
+            args.push(parse_pattern(ctx)?);
+
+            if ctx.get_curr_token().kind() == TokenKind::Comma {
+                ctx.eat(TokenKind::Comma)?;
+            } else if ctx.get_curr_token().kind() != TokenKind::RParen {
+                return ctx.throw_unexpected_token_with_expecting(&TokenKind::RParen);
+            }
+        }
+
+        ctx.eat(TokenKind::RParen)?;
+    }
+
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::VariantPattern {
+        id,
+        args,
+        span: Span::new(span_from, span_to),
+    })
+}
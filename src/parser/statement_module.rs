@@ -1,20 +1,43 @@
-use super::{context::ParsingContext, statements};
+use super::{
+    context::{ParseResult, ParsingContext},
+    statements,
+};
 use crate::{ast, span::Span, token::TokenKind};
 
-pub fn parse(ctx: &mut ParsingContext) -> ast::Mod {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Mod> {
     let span_from = ctx.get_curr_token().span().from;
 
-    ctx.eat(TokenKind::Mod);
+    ctx.eat(TokenKind::Mod)?;
 
+    let path = parse_path(ctx)?;
+
+    ctx.eat(TokenKind::LBrace)?;
+
+    let stmts = statements::parse(ctx)?;
+
+    let span_to = ctx.get_curr_token().span().to;
+
+    ctx.eat(TokenKind::RBrace)?;
+
+    Ok(ast::Mod {
+        path,
+        external: false,
+        stmts,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+/// Parses the dotted path of a mod, `App.Context`.
+pub fn parse_path(ctx: &mut ParsingContext) -> ParseResult<String> {
     let mut paths = vec![];
 
     while ctx.get_curr_token().kind() == TokenKind::Id {
         paths.push(ctx.get_curr_token().value());
 
-        ctx.eat(TokenKind::Id);
+        ctx.eat(TokenKind::Id)?;
 
         if ctx.get_curr_token().kind() == TokenKind::Dot {
-            ctx.eat(TokenKind::Dot);
+            ctx.eat(TokenKind::Dot)?;
         }
 
         if ctx.get_curr_token().kind() == TokenKind::LBrace {
@@ -22,19 +45,5 @@ pub fn parse(ctx: &mut ParsingContext) -> ast::Mod {
         }
     }
 
-    let path = paths.join(".");
-
-    ctx.eat(TokenKind::LBrace);
-
-    let stmts = statements::parse(ctx);
-
-    let span_to = ctx.get_curr_token().span().to;
-
-    ctx.eat(TokenKind::RBrace);
-
-    ast::Mod {
-        path,
-        stmts,
-        span: Span::new(span_from, span_to),
-    }
+    Ok(paths.join("."))
 }
@@ -0,0 +1,58 @@
+use crate::{ast, span::Span, token::TokenKind};
+
+use super::{
+    context::{ParseResult, ParsingContext},
+    identifier,
+};
+
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Record> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Id)?;
+
+    let id = identifier::parse(ctx)?;
+
+    ctx.eat(TokenKind::LBrace)?;
+    ctx.eat_all_newlines()?;
+
+    let mut props = vec![];
+
+    while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RBrace {
+        props.push(parse_prop(ctx)?);
+
+        ctx.eat_all_newlines()?;
+
+        if ctx.get_curr_token().kind() == TokenKind::Comma {
+            ctx.eat(TokenKind::Comma)?;
+            ctx.eat_all_newlines()?;
+        } else if ctx.get_curr_token().kind() != TokenKind::RBrace {
+            return ctx.throw_unexpected_token_with_expecting(&TokenKind::RBrace);
+        }
+    }
+
+    let span_to = ctx.get_curr_token().span().to;
+
+    ctx.eat(TokenKind::RBrace)?;
+
+    Ok(ast::Record {
+        id,
+        props,
+        span: Span::new(span_from, span_to),
+    })
+}
+
+fn parse_prop(ctx: &mut ParsingContext) -> ParseResult<ast::RecProp> {
+    let span_from = ctx.get_curr_token().span().from;
+    let id = identifier::parse(ctx)?;
+
+    ctx.eat(TokenKind::DblColon)?;
+
+    let init = identifier::parse_type(ctx)?;
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::RecProp {
+        id,
+        init,
+        span: Span::new(span_from, span_to),
+    })
+}
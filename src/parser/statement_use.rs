@@ -0,0 +1,37 @@
+use crate::{ast, span::Span, token::TokenKind};
+
+use super::context::{ParseResult, ParsingContext};
+
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Use> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Id)?;
+
+    let path = match ctx.get_curr_token().kind() {
+        TokenKind::Str => {
+            let path = ctx.get_curr_token().value();
+            ctx.eat(TokenKind::Str)?;
+            ast::UsePath::File(path)
+        }
+        _ => {
+            let mut paths = vec![ctx.get_curr_token().value()];
+
+            ctx.eat(TokenKind::Id)?;
+
+            while ctx.get_curr_token().kind() == TokenKind::Dot {
+                ctx.eat(TokenKind::Dot)?;
+                paths.push(ctx.get_curr_token().value());
+                ctx.eat(TokenKind::Id)?;
+            }
+
+            ast::UsePath::Module(paths)
+        }
+    };
+
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::Use {
+        path,
+        span: Span::new(span_from, span_to),
+    })
+}
@@ -1,12 +1,19 @@
-use super::{context::ParsingContext, statement};
+use super::{
+    context::{ParseResult, ParsingContext},
+    statement,
+};
 use crate::{ast, token::TokenKind};
 
-pub fn parse(ctx: &mut ParsingContext) -> Vec<ast::Stmt> {
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<Vec<ast::Stmt>> {
     let mut stmts = vec![];
 
     while ctx.is_not_eof() && ctx.get_curr_token().kind() != TokenKind::RBrace {
-        stmts.push(statement::parse(ctx));
+        let stmt = ctx
+            .parse_or_recover(statement::parse)
+            .unwrap_or_else(ast::Stmt::Error);
+
+        stmts.push(stmt);
     }
 
-    stmts
+    Ok(stmts)
 }
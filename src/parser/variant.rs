@@ -0,0 +1,28 @@
+use crate::{ast, span::Span, token::TokenKind};
+
+use super::{
+    context::{ParseResult, ParsingContext},
+    function_call, identifier,
+};
+
+pub fn parse(ctx: &mut ParsingContext) -> ParseResult<ast::Variant> {
+    let span_from = ctx.get_curr_token().span().from;
+
+    ctx.eat(TokenKind::Dot)?;
+
+    let id = identifier::parse(ctx)?;
+
+    let args = if ctx.get_curr_token().kind() == TokenKind::LParen {
+        function_call::parse(ctx, id.clone())?.args
+    } else {
+        vec![]
+    };
+
+    let span_to = ctx.get_prev_token().span().to;
+
+    Ok(ast::Variant {
+        id,
+        args,
+        span: Span::new(span_from, span_to),
+    })
+}
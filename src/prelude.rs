@@ -0,0 +1,50 @@
+use lazy_static::lazy_static;
+
+use std::rc::Rc;
+
+use crate::{ast, checker, diagnostic::Diagnostic, generator, lexer, loader::UsedFiles, parser};
+
+/// The file the core library is generated into, at the root of the output
+/// directory. Generated files import what they use of the core library from it.
+pub const FILE_NAME: &str = "zac_core.js";
+
+/// The path diagnostics about the core library are labeled with.
+const PATH: &str = "core.zac";
+
+/// The files of `core/`, known to every file without a `use`.
+const SOURCE: &str = concat!(
+    include_str!("../core/Array.zac"),
+    "\n",
+    include_str!("../core/Boolean.zac"),
+    "\n",
+    include_str!("../core/Option.zac"),
+    "\n",
+    include_str!("../core/Result.zac"),
+    "\n",
+    include_str!("../core/String.zac"),
+);
+
+lazy_static! {
+    static ref ROOT: ast::Root = parser::parse(&mut lexer::new(PATH, SOURCE))
+        .unwrap_or_else(|diagnostics| panic!("The core library does not parse: {diagnostics:?}"));
+}
+
+/// The parsed core library, its spans point into the core library rather
+/// than into the files it is used by.
+pub fn root() -> &'static ast::Root {
+    &ROOT
+}
+
+/// Generates the JS of `zac_core.js`.
+pub fn generate() -> Result<String, Diagnostic> {
+    let (code, _) = generator::generate(
+        PATH.into(),
+        SOURCE.into(),
+        root(),
+        None,
+        checker::Methods::new(),
+        Rc::new(UsedFiles::new()),
+    )?;
+
+    Ok(code)
+}
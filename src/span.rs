@@ -1,4 +1,4 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Span {
     pub from: usize,
     pub to: usize,
@@ -17,3 +17,9 @@ impl Span {
 pub trait Spanned {
     fn span(&self) -> Span;
 }
+
+impl Spanned for Span {
+    fn span(&self) -> Span {
+        self.clone()
+    }
+}
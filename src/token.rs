@@ -7,6 +7,7 @@ use crate::span::Span;
 pub enum Token {
     Mod(Span),
     If(Span),
+    Else(Span),
     Fn(Span),
     Id(String, Span),
     Str(String, Span),
@@ -20,8 +21,15 @@ pub enum Token {
     Minus(Span),
     Divide(Span),
     Multiply(Span),
+    AndAnd(Span),
+    OrOr(Span),
+    Pipe(Span),
+    Bang(Span),
     Dot(Span),
     Eq(Span),
+    EqEq(Span),
+    NotEq(Span),
+    Arrow(Span),
     LParen(Span),
     RParen(Span),
     LBrace(Span),
@@ -30,6 +38,8 @@ pub enum Token {
     RSqrBr(Span),
     Gt(Span),
     Lt(Span),
+    GtEq(Span),
+    LtEq(Span),
     DblColon(Span),
     Comma(Span),
     NewLine(Span),
@@ -43,6 +53,7 @@ impl Token {
         match self {
             Token::Mod(_) => String::from("mod"),
             Token::If(_) => String::from("if"),
+            Token::Else(_) => String::from("else"),
             Token::Fn(_) => String::from("fn"),
             Token::Id(v, _) => v.clone(),
             Token::Str(v, _) => v.clone(),
@@ -56,13 +67,22 @@ impl Token {
             Token::Minus(_) => String::from("-"),
             Token::Divide(_) => String::from("/"),
             Token::Multiply(_) => String::from("*"),
+            Token::AndAnd(_) => String::from("&&"),
+            Token::OrOr(_) => String::from("||"),
+            Token::Pipe(_) => String::from("|>"),
+            Token::Bang(_) => String::from("!"),
             Token::Eq(_) => String::from("="),
+            Token::EqEq(_) => String::from("=="),
+            Token::NotEq(_) => String::from("!="),
+            Token::Arrow(_) => String::from("=>"),
             Token::LParen(_) => String::from("("),
             Token::RParen(_) => String::from(")"),
             Token::LBrace(_) => String::from("{"),
             Token::RBrace(_) => String::from("}"),
             Token::Gt(_) => String::from(">"),
             Token::Lt(_) => String::from("<"),
+            Token::GtEq(_) => String::from(">="),
+            Token::LtEq(_) => String::from("<="),
             Token::DblColon(_) => String::from(":"),
             Token::Comma(_) => String::from(","),
             Token::NewLine(_) => String::from("\n"),
@@ -80,6 +100,7 @@ impl Token {
             Token::Mod(s) => s,
             Token::Fn(s) => s,
             Token::If(s) => s,
+            Token::Else(s) => s,
             Token::Id(_, s) => s,
             Token::Str(_, s) => s,
             Token::Numeric(_, s) => s,
@@ -92,13 +113,22 @@ impl Token {
             Token::Minus(s) => s,
             Token::Divide(s) => s,
             Token::Multiply(s) => s,
+            Token::AndAnd(s) => s,
+            Token::OrOr(s) => s,
+            Token::Pipe(s) => s,
+            Token::Bang(s) => s,
             Token::Eq(s) => s,
+            Token::EqEq(s) => s,
+            Token::NotEq(s) => s,
+            Token::Arrow(s) => s,
             Token::LParen(s) => s,
             Token::RParen(s) => s,
             Token::LBrace(s) => s,
             Token::RBrace(s) => s,
             Token::Gt(s) => s,
             Token::Lt(s) => s,
+            Token::GtEq(s) => s,
+            Token::LtEq(s) => s,
             Token::DblColon(s) => s,
             Token::Comma(s) => s,
             Token::NewLine(s) => s,
@@ -118,6 +148,14 @@ impl Token {
     }
 }
 
+/// A line or block comment, kept aside by the lexer so tools like the formatter
+/// can put it back.
+#[derive(Clone, Debug)]
+pub struct Comment {
+    pub value: String,
+    pub span: Span,
+}
+
 impl Default for Token {
     fn default() -> Self {
         Token::Eof(Span::new(0, 0))
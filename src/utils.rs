@@ -26,3 +26,71 @@ pub fn normalize_path(path: &Path) -> PathBuf {
     }
     ret
 }
+
+pub fn common_ancestor(paths: &[PathBuf]) -> PathBuf {
+    let mut paths = paths.iter();
+
+    let mut ancestor = match paths.next() {
+        Some(path) => path.clone(),
+        None => return PathBuf::from("."),
+    };
+
+    for path in paths {
+        while !path.starts_with(&ancestor) {
+            if !ancestor.pop() {
+                break;
+            }
+        }
+    }
+
+    ancestor
+}
+
+/// Returns the zero-based line and character column of the byte offset `pos`.
+pub fn line_col(content: &str, pos: usize) -> (usize, usize) {
+    let pos = pos.min(content.len());
+    let before = content.get(..pos).unwrap_or(content);
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+    (line, before[line_start..].chars().count())
+}
+
+/// Returns the zero-based line and UTF-16 column of the byte offset `pos`, the
+/// way editors and source maps count positions.
+pub fn line_utf16_col(content: &str, pos: usize) -> (usize, usize) {
+    let mut pos = pos.min(content.len());
+
+    while !content.is_char_boundary(pos) {
+        pos -= 1;
+    }
+
+    let before = &content[..pos];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+    (line, before[line_start..].encode_utf16().count())
+}
+
+/// Returns the path of `to` relative to the directory `from`, both absolute.
+pub fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from = from.components().collect::<Vec<_>>();
+    let to = to.components().collect::<Vec<_>>();
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut result = PathBuf::new();
+
+    for _ in common..from.len() {
+        result.push("..");
+    }
+
+    for component in &to[common..] {
+        result.push(component);
+    }
+
+    result
+}
@@ -0,0 +1,51 @@
+use std::{env, fs, process::Command};
+
+use serde_json::{json, Value};
+
+/// Runs `zac build` on `files` written to a directory of their own, returns the
+/// JSON lines it prints.
+fn build_json(name: &str, files: &[(&str, &str)]) -> Vec<Value> {
+    let dir = env::temp_dir().join(format!("zac-cli-{name}-{}", std::process::id()));
+
+    for (path, content) in files {
+        fs::create_dir_all(dir.join(path).parent().unwrap()).unwrap();
+        fs::write(dir.join(path), content).unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_zac"))
+        .current_dir(&dir)
+        .args(["build", "src", "missing.zac", "--message-format=json"])
+        .output()
+        .unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn prints_input_errors_as_json() {
+    let lines = build_json("input", &[("src/index.zac", "let n = 1\n")]);
+
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["message"], "missing.zac does not exist");
+    assert_eq!(lines[0]["severity"], "error");
+    assert_eq!(lines[0]["range"], Value::Null);
+}
+
+#[test]
+fn prints_the_positions_of_notes_like_the_ones_of_diagnostics() {
+    let lines = build_json("notes", &[("src/index.zac", "let n: Number = \"a\"\n")]);
+    let diagnostic = &lines[1];
+    let note = &diagnostic["notes"][0];
+
+    assert_eq!(diagnostic["start"]["column"], 17);
+    assert_eq!(note["message"], "The type is declared here.");
+    assert_eq!(note["range"], json!({ "start": 7, "end": 13 }));
+    assert_eq!(note["start"], json!({ "line": 1, "column": 8 }));
+    assert_eq!(note["end"], json!({ "line": 1, "column": 14 }));
+}
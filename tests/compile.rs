@@ -0,0 +1,594 @@
+use std::{collections::HashMap, sync::Arc};
+
+use zac::{compile, compile_str, Options, Target};
+
+fn code(source: &str) -> String {
+    compile_str(source, &Options::default()).unwrap().code
+}
+
+fn messages(source: &str) -> Vec<String> {
+    compile_str(source, &Options::default())
+        .unwrap_err()
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn compiles_in_memory() {
+    let code = code("fn add(a: Number, b: Number): Number {\n  return a + b\n}\n");
+
+    assert!(
+        code.contains("function add(a, b) {\n  return a + b\n}"),
+        "{code}"
+    );
+}
+
+#[test]
+fn leaves_the_prelude_out() {
+    let options = Options {
+        prelude: None,
+        ..Options::default()
+    };
+    let output = compile_str("let n = 1\n", &options).unwrap();
+
+    assert!(!output.code.contains("zac_core"), "{}", output.code);
+}
+
+#[test]
+fn reports_every_error_at_once() {
+    let messages = messages("let a: Number = \"a\"\nlet b: String = 1\n");
+
+    assert_eq!(
+        messages,
+        [
+            "The variable a is declared as Number, found String.",
+            "The variable b is declared as String, found Number.",
+        ]
+    );
+}
+
+#[test]
+fn reports_syntax_errors_with_their_span() {
+    let diagnostics = compile_str("let = 1\n", &Options::default()).unwrap_err();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].file, "<anonymous>");
+    assert_eq!(diagnostics[0].span.from, 4);
+}
+
+fn with_files(files: &[(&str, &str)]) -> Options {
+    let files = files
+        .iter()
+        .map(|(path, content)| (path.to_string(), content.to_string()))
+        .collect::<HashMap<_, _>>();
+
+    Options {
+        loader: Arc::new(files),
+        ..Options::default()
+    }
+}
+
+#[test]
+fn reads_used_files_through_the_loader() {
+    let options = with_files(&[(
+        "src/lib.zac",
+        "pub fn double(n: Number): Number {\n  return n * 2\n}\n",
+    )]);
+    let source = "use \"./lib.zac\"\nlet n: Number = double(2)\n";
+    let code = compile("src/index.zac", source, &options).unwrap().code;
+
+    assert!(
+        code.contains("import { double } from \"./lib.jsx\""),
+        "{code}"
+    );
+    assert!(code.contains("let n = double(2)"), "{code}");
+}
+
+#[test]
+fn checks_the_types_of_used_fns() {
+    let options = with_files(&[(
+        "src/lib.zac",
+        "pub fn name(): String {\n  return \"a\"\n}\n",
+    )]);
+    let source = "use \"./lib.zac\"\nlet n: Number = name()\n";
+    let diagnostics = compile("src/index.zac", source, &options).unwrap_err();
+
+    assert_eq!(
+        diagnostics[0].message,
+        "The variable n is declared as Number, found String."
+    );
+}
+
+#[test]
+fn reports_used_files_the_loader_cannot_find() {
+    let diagnostics =
+        compile("src/index.zac", "use \"./lib.zac\"\n", &Options::default()).unwrap_err();
+
+    assert!(
+        diagnostics[0]
+            .message
+            .starts_with("Cannot read src/lib.zac"),
+        "{}",
+        diagnostics[0].message
+    );
+}
+
+#[test]
+fn generates_if_expressions_as_conditionals() {
+    let code = code("fn sign(n: Number): String {\n  return if n > 0 { \"+\" } else if n < 0 { \"-\" } else { \"0\" }\n}\n");
+
+    assert!(
+        code.contains("return (n > 0 ? `+` : (n < 0 ? `-` : `0`))"),
+        "{code}"
+    );
+}
+
+#[test]
+fn generates_if_expressions_with_statements_as_arrow_fns() {
+    let code = code("fn f(n: Number): Number {\n  return if n > 0 {\n    let d = n * 2\n    d\n  } else {\n    0\n  }\n}\n");
+
+    assert!(code.contains("return (() => {\n    if (n > 0) {\n      let d = n * 2\n      return d\n    } else {\n      return 0\n    }\n  })()"), "{code}");
+}
+
+#[test]
+fn generates_if_statements() {
+    let code = code("fn f(n: Number) {\n  if n == 0 {\n    print(n)\n  }\n}\n");
+
+    assert!(code.contains("if (n === 0) {\n    print(n)\n  }"), "{code}");
+}
+
+#[test]
+fn reports_if_values_without_else() {
+    assert_eq!(
+        messages("let a = if true { 1 }\n"),
+        ["An if used as a value needs an else branch."]
+    );
+}
+
+#[test]
+fn checks_if_conditions_and_values() {
+    assert_eq!(
+        messages("let a = if 1 { 1 } else { 2 }\nlet b: String = if true { 1 } else { 2 }\n"),
+        [
+            "The condition of an if expects Boolean, found Number.",
+            "The variable b is declared as String, found Number.",
+        ]
+    );
+}
+
+const USER: &str = "record User { name: String, age: Number }\n";
+
+#[test]
+fn generates_records_as_plain_objects() {
+    let code = code(&format!(
+        "{USER}fn older(u: User): User {{\n  return User {{ ...u, age: u.age + 1 }}\n}}\nlet u = User {{ name: \"a\", age: 3 }}\n"
+    ));
+
+    assert!(code.contains("return { ...u, age: u.age + 1 }"), "{code}");
+    assert!(code.contains("let u = { name: `a`, age: 3 }"), "{code}");
+}
+
+#[test]
+fn reads_the_fields_of_records() {
+    let code = code(&format!(
+        "{USER}let u = User {{ name: \"a\", age: 3 }}\nlet a = u.name\nlet b = \"hi #{{u.name}}\"\nfn name(v: User): String {{\n  return v.name\n}}\n"
+    ));
+
+    assert!(code.contains("let a = u.name"), "{code}");
+    assert!(code.contains("let b = `hi ${u.name}`"), "{code}");
+    assert!(code.contains("return v.name"), "{code}");
+}
+
+#[test]
+fn reports_the_fields_records_do_not_declare() {
+    let source = format!(
+        "{USER}let u = User {{ name: \"a\", name: \"b\", nope: 1 }}\nlet v = User {{ age: 3 }}\nlet w = Nobody {{}}\n"
+    );
+
+    assert_eq!(
+        messages(&source),
+        [
+            "The field name is set more than once.",
+            "The record User has no field nope.",
+            "The record User is missing the field(s) age.",
+            "The record User is missing the field(s) name.",
+            "The record Nobody is not defined.",
+        ]
+    );
+}
+
+#[test]
+fn checks_the_values_of_fields() {
+    assert_eq!(
+        messages(&format!("{USER}let u = User {{ name: 1, age: 3 }}\n")),
+        ["The field name of User expects String, found Number."]
+    );
+}
+
+const SHAPE: &str = "enum Shape { Circle(Number), Square(Number), Dot }\n";
+
+#[test]
+fn generates_enums_as_tagged_constructors() {
+    let code = code(&format!(
+        "{SHAPE}let a = Shape.Circle(2)\nlet b = Shape.Dot\nlet c: Shape = .Square(3)\n"
+    ));
+
+    assert!(
+        code.contains("function Shape_Circle(_0) {\n  return { $tag: \"Circle\", 0: _0 }\n}"),
+        "{code}"
+    );
+    assert!(
+        code.contains("const Shape_Dot = { $tag: \"Dot\" }"),
+        "{code}"
+    );
+    assert!(code.contains("let a = Shape_Circle(2)"), "{code}");
+    assert!(code.contains("let b = Shape_Dot"), "{code}");
+    assert!(code.contains("let c = Shape_Square(3)"), "{code}");
+}
+
+#[test]
+fn reports_the_variants_enums_do_not_declare() {
+    let source = format!(
+        "{SHAPE}enum Other {{ Dot }}\nlet a = Shape.Nope\nlet b = Shape.Circle\nlet c = .Dot\nlet d = .Nope\nlet e = Shape.Circle(\"a\")\n"
+    );
+
+    assert_eq!(
+        messages(&source),
+        [
+            "The enum Shape has no variant Nope.",
+            "The variant Shape.Circle takes 1 value(s) but 0 were given.",
+            "The variant Dot is ambiguous, it is defined by Other, Shape. Write Other.Dot instead.",
+            "No enum has the variant Nope.",
+            "The variant Shape.Circle expects Number, found String.",
+        ]
+    );
+}
+
+#[test]
+fn generates_match_expressions_testing_tags() {
+    let code = code(&format!(
+        "{SHAPE}fn area(s: Shape): Number {{\n  return match s {{\n    .Circle(r) => r * r * 3,\n    .Square(a) => a * a,\n    .Dot => 0,\n  }}\n}}\n"
+    ));
+
+    assert!(
+        code.contains(
+            "  return (($match) => {\n    if ($match.$tag === \"Circle\") {\n      let r = $match[0]\n      return r * r * 3\n    } else if ($match.$tag === \"Square\") {"
+        ),
+        "{code}"
+    );
+    assert!(code.contains("  })(s)"), "{code}");
+}
+
+#[test]
+fn generates_match_statements_on_literals_and_bindings() {
+    let code = code(
+        "fn f(n: Number) {\n  match n {\n    0 => print(\"zero\"),\n    x => print(x),\n  }\n}\n",
+    );
+
+    assert!(
+        code.contains("    let $match = n\n    if ($match === 0) {\n      print(`zero`)\n    } else {\n      let x = $match\n      print(x)\n    }"),
+        "{code}"
+    );
+}
+
+#[test]
+fn reports_the_cases_a_match_misses() {
+    let source = format!(
+        "{SHAPE}fn a(s: Shape): Number {{\n  return match s {{\n    .Circle(r) => r,\n  }}\n}}\nfn b(n: Boolean): Number {{\n  return match n {{\n    true => 1,\n  }}\n}}\nfn c(n: Number): Number {{\n  return match n {{\n    0 => 1,\n  }}\n}}\n"
+    );
+
+    assert_eq!(
+        messages(&source),
+        [
+            "This match does not handle every case, it is missing .Square(_), .Dot.",
+            "This match does not handle every case, it is missing false.",
+            "This match does not handle every case, it is missing a `_` arm.",
+        ]
+    );
+}
+
+#[test]
+fn reports_the_patterns_a_match_cannot_use() {
+    let source = format!(
+        "{SHAPE}fn a(s: Shape): Number {{\n  return match s {{\n    .Square(a, b) => a,\n    .Nope => 0,\n    _ => 1,\n    .Dot => 2,\n  }}\n}}\n"
+    );
+
+    assert_eq!(
+        messages(&source),
+        [
+            "The variant Shape.Square carries 1 value(s) but the pattern has 2.",
+            "No enum has the variant Nope.",
+            "This arm is never reached, the arm before it matches every value.",
+        ]
+    );
+}
+
+#[test]
+fn keeps_only_the_parentheses_precedence_needs() {
+    let code = code(
+        "let a = (1 * 2) + 3\nlet b = 1 - (2 - 3)\nlet c = (1 - 2) - 3\nlet d = (1 + 2) * 3\n",
+    );
+
+    assert!(code.contains("let a = 1 * 2 + 3"), "{code}");
+    assert!(code.contains("let b = 1 - (2 - 3)"), "{code}");
+    assert!(code.contains("let c = 1 - 2 - 3"), "{code}");
+    assert!(code.contains("let d = (1 + 2) * 3"), "{code}");
+}
+
+#[test]
+fn generates_strict_equality() {
+    let code = code("let a = 2 == 2 && 3 != 4 || !true\n");
+
+    assert!(
+        code.contains("let a = 2 === 2 && 3 !== 4 || !true"),
+        "{code}"
+    );
+}
+
+#[test]
+fn checks_operands_by_precedence() {
+    assert_eq!(
+        messages("let a: Boolean = 1 + 2 < 4 == true\nlet b = 1 + \"a\"\nlet c = !1\nlet d = 1 && true\nlet e = \"a\" < 2\n"),
+        [
+            "The operator + expects Number, found String.",
+            "The operator ! expects Boolean, found Number.",
+            "The operator && expects Boolean, found Number.",
+            "Values of type String and Number cannot be compared.",
+        ]
+    );
+}
+
+#[test]
+fn reports_fns_ending_without_a_return() {
+    let source = "fn add(a: Number, b: Number): Number {\n  a + b\n}\nfn half(a: Number): Number {\n  if a > 0 {\n    return 1\n  }\n}\nfn both(a: Number): Number {\n  if a > 0 {\n    return 1\n  } else {\n    return 2\n  }\n}\nfn unit(a: Number): Unit {\n  print(a)\n}\n";
+
+    assert_eq!(
+        messages(source),
+        [
+            "The function add returns Number but can end without returning a value.",
+            "The function half returns Number but can end without returning a value.",
+        ]
+    );
+}
+
+#[test]
+fn types_the_fields_of_records_like_the_generator_reads_them() {
+    let source = format!("{USER}fn initial(u: User): Number {{\n  return u.name.len()\n}}\n");
+    let code = code(&source);
+
+    assert!(code.contains("return String_len(u.name)"), "{code}");
+    assert_eq!(
+        messages(&format!(
+            "{USER}fn age(u: User): Number {{\n  let n: Number = u.name\n  return u.nope\n}}\n"
+        )),
+        [
+            "The variable n is declared as Number, found String.",
+            "The record User has no field nope.",
+        ]
+    );
+}
+
+#[test]
+fn points_notes_at_the_file_of_used_fns() {
+    let options = with_files(&[(
+        "src/components/Hello.zac",
+        "pub fn hello() {\n  log(\"hi\")\n}\n",
+    )]);
+    let source = "use \"./components/Hello.zac\"\nhello(3)\n";
+    let diagnostics = compile("src/index.zac", source, &options).unwrap_err();
+    let note = &diagnostics[0].notes[0];
+
+    assert_eq!(note.file, "src/components/Hello.zac");
+    assert_eq!(note.span.as_ref().map(|span| span.from), Some(0));
+}
+
+#[test]
+fn passes_mod_fns_as_values() {
+    let source = "mod M {\n  pub fn inc(s: String): String {\n    return s\n  }\n}\nfn apply(f: Fn<String, Number>, s: String): Number {\n  return f(s)\n}\nlet n = apply(String.len, \"x\")\n";
+
+    assert!(code(source).contains("let n = apply(String_len, `x`)"));
+    assert_eq!(
+        messages(&format!("{source}let m = apply(M.inc, \"x\")\n")),
+        ["The argument f of apply expects Fn<String, Number>, found Fn<String, String>."]
+    );
+    assert_eq!(
+        messages(&format!("{source}let m = apply(M.nope, \"x\")\n")),
+        ["The mod M has no function or variable nope."]
+    );
+}
+
+#[test]
+fn reports_placeholders_outside_pipe_targets() {
+    let source = "fn f(a: Number, b: Number): Number {\n  return a + b\n}\n";
+
+    assert!(code(&format!("{source}let y = 1 |> f(_, 2)\n")).contains("let y = f(1, 2)"));
+    assert_eq!(
+        messages(&format!("{source}let y = f(_, 2)\n")),
+        ["`_` only stands for the piped value, as one argument of the function after `|>`."]
+    );
+    assert_eq!(
+        messages(&format!("{source}let y = 1 |> _.await\n")),
+        ["Only a function can be piped into, write `value |> f` or `value |> f(_, 2)` with one `_` where the value goes."]
+    );
+}
+
+#[test]
+fn generates_es_imports() {
+    let code = code("import React, { a as b: Fn<Number, Number>, c } from \"./x.js\"\nimport * as path from \"path\"\nlet y = b(2)\nlet p = path.join(\"a\", \"b\")\n");
+
+    assert!(
+        code.contains("import React, { a as b, c } from \"./x.js\""),
+        "{code}"
+    );
+    assert!(code.contains("import * as path from \"path\""), "{code}");
+    assert!(code.contains("let y = b(2)"), "{code}");
+    assert!(code.contains("let p = path.join(`a`, `b`)"), "{code}");
+}
+
+#[test]
+fn reports_broken_interpolations_on_their_closing_brace() {
+    let empty = compile_str("let s = \"x #{} y\"\n", &Options::default()).unwrap_err();
+    let cut = compile_str("let s = \"x #{1 +} y\"\n", &Options::default()).unwrap_err();
+
+    assert_eq!(
+        empty[0].message,
+        "The interpolation is empty, write the value to insert between `#{` and `}`."
+    );
+    assert_eq!((empty[0].span.from, empty[0].span.to), (13, 14));
+    assert_eq!(cut[0].message, "Unexpected end of interpolation.");
+    assert_eq!((cut[0].span.from, cut[0].span.to), (16, 17));
+}
+
+#[test]
+fn instantiates_the_output_of_generic_fns() {
+    let source = "fn first<a>(arr: Array<a>): a {\n  return `arr[0]`\n}\n";
+
+    assert!(
+        code(&format!("{source}let s: String = first([\"a\"])\n")).contains("let s = first([`a`])")
+    );
+    assert_eq!(
+        messages(&format!("{source}let s: Number = first([\"a\"])\n")),
+        ["The variable s is declared as Number, found String."]
+    );
+}
+
+#[test]
+fn infers_the_args_of_lambdas_from_fn_types() {
+    let source = "fn apply(f: Fn<String, Number>, s: String): Number {\n  return f(s)\n}\n";
+
+    assert!(code(&format!(
+        "{source}let n = apply((s) {{\n  return s.len()\n}}, \"x\")\n"
+    ))
+    .contains("return String_len(s)"));
+    assert_eq!(
+        messages(&format!(
+            "{source}let n = apply((s) {{\n  return s\n}}, \"x\")\nlet m = apply((a, b) {{\n  return 1\n}}, \"x\")\n"
+        )),
+        [
+            "This function returns Number, found String.",
+            "This function takes 2 argument(s) but Fn<String, Number> takes 1."
+        ]
+    );
+}
+
+#[test]
+fn lowers_pipes_to_nested_calls() {
+    let source = "fn f(n: Number, m: Number): Boolean {\n  return n > m\n}\nlet s = \"abc\"\n";
+    let code = code(&format!(
+        "{source}let b = s |> String.len |> f(_, 2)\nlet t = b |> Boolean.toString\n"
+    ));
+
+    assert!(code.contains("let b = f(String_len(s), 2)"), "{code}");
+    assert!(code.contains("let t = Boolean_toString(b)"), "{code}");
+    assert_eq!(
+        messages(&format!("{source}let c: Boolean = s |> String.len\n")),
+        ["The variable c is declared as Boolean, found Number."]
+    );
+}
+
+#[test]
+fn imports_the_core_fns_it_uses() {
+    let code = code("let s = \"abc\"\nlet n = s.len()\n");
+
+    assert!(
+        code.starts_with("import { String_len } from \"./zac_core.js\""),
+        "{code}"
+    );
+    assert!(code.contains("let n = String_len(s)"), "{code}");
+    assert!(!code.contains("function String_len"), "{code}");
+}
+
+#[test]
+fn calls_externs_by_their_js_names() {
+    let source = "extern fn parseInt(s: String): Number\nextern mod Deno {\n  fn readTextFile(path: String): Promise<String>\n}\n";
+    let code = code(&format!(
+        "{source}let n: Number = parseInt(\"1\")\nlet text = Deno.readTextFile(\"a\")\n"
+    ));
+
+    assert!(code.contains("let n = parseInt(`1`)"), "{code}");
+    assert!(code.contains("let text = Deno.readTextFile(`a`)"), "{code}");
+    assert!(!code.contains("function"), "{code}");
+    assert_eq!(
+        messages(&format!("{source}let n: String = parseInt(1)\n")),
+        [
+            "The argument s of parseInt expects String, found Number.",
+            "The variable n is declared as String, found Number."
+        ]
+    );
+}
+
+#[test]
+fn exports_pub_fns_and_lets() {
+    let code = code("pub fn a(): Number {\n  return 1\n}\nfn b(): Number {\n  return 2\n}\npub let c = 3\nmod M {\n  pub fn d(): Number {\n    return 4\n  }\n  fn e(): Number {\n    return 5\n  }\n}\n");
+
+    assert!(code.contains("export function a()"), "{code}");
+    assert!(code.contains("\nfunction b()"), "{code}");
+    assert!(code.contains("export let c = 3"), "{code}");
+    assert!(code.contains("export function M_d()"), "{code}");
+    assert!(code.contains("\nfunction M_e()"), "{code}");
+}
+
+#[test]
+fn keeps_fns_without_pub_private() {
+    let options = with_files(&[("src/lib.zac", "fn secret(): Number {\n  return 1\n}\n")]);
+    let source = "use \"./lib.zac\"\nlet n = secret()\n";
+    let diagnostics = compile("src/index.zac", source, &options).unwrap_err();
+
+    assert_eq!(
+        diagnostics[0].message,
+        "The function secret is not pub in src/lib.zac, other files cannot use it."
+    );
+}
+
+#[test]
+fn makes_fns_using_await_async() {
+    let source = "extern fn fetch(url: String): Promise<String>\nfn load(): String {\n  return fetch(\"a\").await\n}\nlet f = () {\n  fetch(\"b\").await\n}\n";
+    let code = code(source);
+
+    assert!(code.contains("async function load()"), "{code}");
+    assert!(code.contains("let f = async () => {"), "{code}");
+    assert_eq!(
+        messages(&format!("{source}let x: String = load()\n")),
+        ["The variable x is declared as String, found Promise<String>."]
+    );
+}
+
+#[test]
+fn allows_top_level_await_in_modules_only() {
+    let source = "extern fn fetch(url: String): Promise<String>\nlet text = fetch(\"a\").await\n";
+    let script = Options {
+        target: Target::Script,
+        ..Options::default()
+    };
+    let diagnostics = compile_str(source, &script).unwrap_err();
+
+    assert!(code(source).contains("let text = (await fetch(`a`))"));
+    assert_eq!(
+        diagnostics[0].message,
+        "Top-level .await is only allowed when the target is an ES module."
+    );
+}
+
+#[test]
+fn generates_and_checks_each_interpolation() {
+    let source = "let a = 1\nlet b = \"x\"\n";
+    let code = code(&format!(
+        "{source}let s = \"#{{a}} and #{{b.len()}} and #{{a + 1}}\"\n"
+    ));
+    let diagnostics = compile_str(
+        &format!("{source}let s = \"n: #{{a - b}}\"\n"),
+        &Options::default(),
+    )
+    .unwrap_err();
+
+    assert!(
+        code.contains("let s = `${a} and ${String_len(b)} and ${a + 1}`"),
+        "{code}"
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "The operator - expects Number, found String."
+    );
+    assert_eq!(diagnostics[0].span.from, 40);
+}
@@ -0,0 +1,136 @@
+use std::{
+    io::{Read, Write},
+    process::{Command, Stdio},
+};
+
+use serde_json::{json, Value};
+
+const URI: &str = "file:///project/index.zac";
+
+fn frame(message: &Value) -> Vec<u8> {
+    let body = message.to_string();
+
+    format!("Content-Length: {}\r\n\r\n{body}", body.len()).into_bytes()
+}
+
+/// Sends `messages` to `zac lsp` and returns its exit code along with the
+/// messages it answered with.
+fn session(messages: &[Value]) -> (Option<i32>, Vec<Value>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_zac"))
+        .arg("lsp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdin = child.stdin.take().unwrap();
+    for message in messages {
+        stdin.write_all(&frame(message)).unwrap();
+    }
+    drop(stdin);
+
+    let mut output = String::new();
+    child
+        .stdout
+        .take()
+        .unwrap()
+        .read_to_string(&mut output)
+        .unwrap();
+    let status = child.wait().unwrap();
+
+    let mut responses = vec![];
+    let mut rest = output.as_str();
+
+    while let Some((headers, tail)) = rest.split_once("\r\n\r\n") {
+        let length = headers
+            .lines()
+            .find_map(|line| line.strip_prefix("Content-Length: "))
+            .unwrap()
+            .parse::<usize>()
+            .unwrap();
+
+        responses.push(serde_json::from_str(&tail[..length]).unwrap());
+        rest = &tail[length..];
+    }
+
+    (status.code(), responses)
+}
+
+fn initialize() -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} })
+}
+
+fn did_open(text: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": { "uri": URI, "languageId": "zac", "version": 1, "text": text }
+        }
+    })
+}
+
+fn shutdown() -> Value {
+    json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" })
+}
+
+fn exit() -> Value {
+    json!({ "jsonrpc": "2.0", "method": "exit" })
+}
+
+#[test]
+fn publishes_diagnostics_with_utf16_positions() {
+    // The emoji takes two UTF-16 code units and four bytes.
+    let (code, responses) = session(&[
+        initialize(),
+        did_open("let s = \"😀\" let n: Number = \"x\"\n"),
+        shutdown(),
+        exit(),
+    ]);
+
+    assert_eq!(code, Some(0));
+    assert_eq!(responses.len(), 3);
+
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(
+        responses[0]["result"]["capabilities"]["textDocumentSync"]["change"],
+        1
+    );
+
+    assert_eq!(responses[1]["method"], "textDocument/publishDiagnostics");
+    assert_eq!(responses[1]["params"]["uri"], URI);
+
+    let diagnostics = responses[1]["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]["message"],
+        "The variable n is declared as Number, found String."
+    );
+    assert_eq!(
+        diagnostics[0]["range"],
+        json!({
+            "start": { "line": 0, "character": 29 },
+            "end": { "line": 0, "character": 32 }
+        })
+    );
+
+    assert_eq!(
+        responses[2],
+        json!({ "jsonrpc": "2.0", "id": 2, "result": null })
+    );
+}
+
+#[test]
+fn publishes_no_diagnostics_for_valid_documents() {
+    let (_, responses) = session(&[initialize(), did_open("let n = 1\n"), shutdown(), exit()]);
+
+    assert_eq!(responses[1]["params"]["diagnostics"], json!([]));
+}
+
+#[test]
+fn exits_with_an_error_without_shutdown() {
+    let (code, responses) = session(&[initialize(), exit()]);
+
+    assert_eq!(code, Some(1));
+    assert_eq!(responses.len(), 1);
+}